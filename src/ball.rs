@@ -1,11 +1,21 @@
+use crate::color::ColorMode;
 #[cfg(feature = "debug")]
 use crate::paddle::Direction;
 use crate::rectf64::Rectf64;
+use crate::render::GameRenderer;
 use ratatui::style::Color;
-use ratatui::widgets::canvas::{Circle, Painter, Shape};
+#[cfg(feature = "tui")]
+use ratatui::widgets::canvas::{Painter, Shape};
+
+/// The time, in seconds, between the points of a predicted path.
+pub const PREDICTION_STEP: f64 = 0.1;
+
+/// How many times longer the motion blur streak is drawn while the ball is on fire.
+const FIRE_TRAIL: f64 = 3.0;
 
 /// Represents a ball with a position, radius, and velocity.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ball {
     /// The x-coordinate of the ball's center.
     x: f64,
@@ -13,10 +23,26 @@ pub struct Ball {
     y: f64,
     /// The radius of the ball.
     radius: f64,
-    /// The velocity of the ball along the x-axis.
+    /// The velocity of the ball along the x-axis, in units per second.
     vx: f64,
-    /// The velocity of the ball along the y-axis.
+    /// The velocity of the ball along the y-axis, in units per second.
     vy: f64,
+    /// The share of velocity kept after a bounce.
+    restitution: f64,
+    /// How long, in seconds, the ball takes to accelerate to its full speed after launch.
+    ease_in: f64,
+    /// The time, in seconds, the ball has been moving for.
+    age: f64,
+    /// The time, in seconds, since the ball last hit the paddle or a brick.
+    airtime: f64,
+    /// Whether the ball is drawn on fire, as a reward for a streak of broken bricks.
+    on_fire: bool,
+    /// The position of the ball's center before it last moved.
+    previous: (f64, f64),
+    /// Whether a motion blur streak is drawn from the previous position.
+    motion_blur: bool,
+    /// The share of its speed the ball keeps moving vertically with after a bounce.
+    min_vertical: f64,
 }
 
 impl Ball {
@@ -38,26 +64,165 @@ impl Ball {
             radius,
             vx,
             vy,
+            restitution: 1.,
+            ease_in: 0.,
+            age: 0.,
+            airtime: 0.,
+            on_fire: false,
+            previous: (x, y),
+            motion_blur: false,
+            min_vertical: 0.,
         }
     }
 
-    /// Moves the ball based on its velocity
-    pub fn mov(&mut self) {
-        #[cfg(feature = "debug")]
-        let old_x = self.x;
-        #[cfg(feature = "debug")]
-        let old_y = self.y;
+    /// Makes the ball accelerate from standstill to its full speed after launch.
+    ///
+    /// # Parameters
+    /// - `duration`: How long, in seconds, the acceleration takes. `0.0` launches the ball at
+    ///   full speed.
+    ///
+    /// # Returns
+    /// The updated `Ball`.
+    pub fn ease_in(mut self, duration: f64) -> Self {
+        self.ease_in = duration;
+        self
+    }
+
+    /// Draws a streak behind the ball, from where it was before its last move.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to draw the streak.
+    ///
+    /// # Returns
+    /// The updated `Ball`.
+    pub fn motion_blur(mut self, enabled: bool) -> Self {
+        self.motion_blur = enabled;
+        self
+    }
+
+    /// Keeps the ball from settling into a nearly horizontal path, bouncing between the side
+    /// walls forever: after every bounce, its velocity is turned so that at least the given
+    /// share of its speed is vertical.
+    ///
+    /// # Parameters
+    /// - `share`: The share of the speed, between `0.0` and `1.0`.
+    ///
+    /// # Returns
+    /// The updated `Ball`.
+    pub fn min_vertical_speed(mut self, share: f64) -> Self {
+        self.min_vertical = share.clamp(0., 1.);
+        self
+    }
+
+    /// Sets the coefficient of restitution applied on every bounce.
+    ///
+    /// # Parameters
+    /// - `restitution`: The share of velocity kept after a bounce. Values below `1.0` make
+    ///   bounces lose energy, values above `1.0` make them gain it.
+    ///
+    /// # Returns
+    /// The updated `Ball`.
+    pub fn restitution(mut self, restitution: f64) -> Self {
+        self.restitution = restitution;
+        self
+    }
+
+    /// Puts the ball at the given position, as if it was just launched from there.
+    ///
+    /// # Parameters
+    /// - `x`: The new x-coordinate of the ball's center.
+    /// - `y`: The new y-coordinate of the ball's center.
+    pub fn place(&mut self, x: f64, y: f64) {
+        (self.x, self.y) = (x, y);
+        self.previous = (x, y);
+        self.age = 0.;
+        self.airtime = 0.;
+    }
+
+    /// Returns the position of the ball's center.
+    ///
+    /// # Returns
+    /// The `(x, y)` coordinates of the ball's center.
+    pub fn position(&self) -> (f64, f64) {
+        (self.x, self.y)
+    }
+
+    /// Returns the velocity of the ball.
+    ///
+    /// # Returns
+    /// The `(vx, vy)` velocity, in units per second.
+    pub fn velocity(&self) -> (f64, f64) {
+        (self.vx, self.vy)
+    }
+
+    /// Returns the speed the ball currently moves with, including the launch ease-in.
+    ///
+    /// # Returns
+    /// The speed, in units per second.
+    pub fn speed(&self) -> f64 {
+        self.vx.hypot(self.vy) * self.ease_factor()
+    }
+
+    /// Returns the share of the full speed the ball moves with during the launch ease-in.
+    fn ease_factor(&self) -> f64 {
+        if self.ease_in > 0. {
+            (self.age / self.ease_in).min(1.)
+        } else {
+            1.
+        }
+    }
+
+    /// Sets whether the ball is on fire.
+    ///
+    /// # Parameters
+    /// - `on_fire`: `true` to draw the ball in a hotter color, with a longer trail, and let
+    ///   it break the multi-hit bricks in a single hit.
+    pub fn set_on_fire(&mut self, on_fire: bool) {
+        self.on_fire = on_fire;
+    }
+
+    /// Returns whether the ball is on fire, breaking the bricks in a single hit.
+    pub fn is_on_fire(&self) -> bool {
+        self.on_fire
+    }
+
+    /// Returns how long the ball has been flying since it last hit the paddle or a brick.
+    ///
+    /// # Returns
+    /// The time, in seconds.
+    pub fn airtime(&self) -> f64 {
+        self.airtime
+    }
 
-        self.x += self.vx;
-        self.y += self.vy;
+    /// Starts measuring the ball's airtime over, once it hits the paddle or a brick.
+    pub fn reset_airtime(&mut self) {
+        self.airtime = 0.;
+    }
+
+    /// Returns the radius of the ball.
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// Moves the ball based on its velocity
+    ///
+    /// # Parameters
+    /// - `dt`: The time delta for the movement, in seconds.
+    pub fn mov(&mut self, dt: f64) {
+        self.previous = (self.x, self.y);
+        self.age += dt;
+        self.airtime += dt;
+        let ease = self.ease_factor();
+        self.x += self.vx * ease * dt;
+        self.y += self.vy * ease * dt;
 
         #[cfg(feature = "debug")]
         tracing::trace!(
             "Moved the ball (vx: {}, vy: {}): {},{} -> {},{}",
             self.vx,
             self.vy,
-            old_x,
-            old_y,
+            self.previous.0,
+            self.previous.1,
             self.x,
             self.y
         );
@@ -84,15 +249,74 @@ impl Ball {
     /// Reverses the ball's velocity along the y-axis, simulating a vertical bounce.
     pub fn bouncev(&mut self) {
         #[cfg(feature = "debug")]
-        tracing::trace!("Bounce the ball vertically: {} -> {}", self.vy, -self.vy,);
-        self.vy = -self.vy;
+        tracing::trace!(
+            "Bounce the ball vertically: {} -> {}",
+            self.vy,
+            -self.vy * self.restitution
+        );
+        self.vy = -self.vy * self.restitution;
+    }
+
+    /// Reflects the ball's velocity off a surface, as if bouncing off it.
+    ///
+    /// Only the velocity component along the normal is reversed, and scaled by the
+    /// restitution. A ball already moving away from the surface isn't affected.
+    ///
+    /// # Parameters
+    /// - `normal`: The unit normal of the surface, pointing towards the ball.
+    pub fn reflect(&mut self, normal: (f64, f64)) {
+        self.reflect_with(normal, self.restitution);
+    }
+
+    /// Reflects the ball's velocity off a surface like [`Ball::reflect`], but keeps all of
+    /// its speed whatever the restitution, e.g. off the paddle.
+    ///
+    /// # Parameters
+    /// - `normal`: The unit normal of the surface, pointing towards the ball.
+    pub fn rebound(&mut self, normal: (f64, f64)) {
+        self.reflect_with(normal, 1.);
     }
 
-    /// Reverses the ball's velocity along the x-axis, simulating a horizontal bounce.
-    pub fn bounceh(&mut self) {
+    /// Reflects the ball's velocity off a surface with the given restitution.
+    fn reflect_with(&mut self, normal: (f64, f64), restitution: f64) {
+        let (nx, ny) = normal;
+        let dot = self.vx * nx + self.vy * ny;
+        if dot >= 0. {
+            return;
+        }
         #[cfg(feature = "debug")]
-        tracing::trace!("Bounce the ball horizontally: {} -> {}", self.vx, -self.vx,);
-        self.vx = -self.vx;
+        tracing::trace!("Reflect the ball off the normal {normal:?}");
+        let k = (1. + restitution) * dot;
+        self.vx -= k * nx;
+        self.vy -= k * ny;
+    }
+
+    /// Computes the normal of a shape's surface at the point the ball touches it.
+    ///
+    /// # Parameters
+    /// - `shape`: The shape the ball touches.
+    ///
+    /// # Returns
+    /// The unit normal pointing from the closest point of the shape towards the ball's center.
+    /// If the center is inside the shape, the normal of the closest edge.
+    pub fn contact_normal<EC: EllasticCollision>(&self, shape: &EC) -> (f64, f64) {
+        let area = shape.area();
+        let dx = self.x - self.x.clamp(area.left(), area.right());
+        let dy = self.y - self.y.clamp(area.bottom(), area.top());
+        let distance = dx.hypot(dy);
+        if distance > 0. {
+            return (dx / distance, dy / distance);
+        }
+        [
+            (self.x - area.left(), (-1., 0.)),
+            (area.right() - self.x, (1., 0.)),
+            (self.y - area.bottom(), (0., -1.)),
+            (area.top() - self.y, (0., 1.)),
+        ]
+        .into_iter()
+        .min_by(|(d1, _), (d2, _)| d1.total_cmp(d2))
+        .map(|(_, normal)| normal)
+        .unwrap_or((0., -1.))
     }
 
     /// Changes the ball's velocity along the x-axis by the given amount.
@@ -109,20 +333,173 @@ impl Ball {
         self.vx += dvx;
     }
 
+    /// Slows the ball down, keeping its direction.
+    ///
+    /// # Parameters
+    /// - `factor`: The share of its speed the ball keeps, between `0.0` and `1.0`.
+    pub fn slow_down(&mut self, factor: f64) {
+        let factor = factor.clamp(0., 1.);
+        self.vx *= factor;
+        self.vy *= factor;
+    }
+
+    /// Speeds the ball up, keeping its direction.
+    ///
+    /// # Parameters
+    /// - `dv`: The speed to add, in units per second.
+    /// - `max`: The speed the ball isn't accelerated past. A ball already faster keeps its
+    ///   speed.
+    pub fn accelerate(&mut self, dv: f64, max: f64) {
+        let speed = self.vx.hypot(self.vy);
+        if speed == 0. {
+            return;
+        }
+        let factor = (speed + dv).min(max).max(speed) / speed;
+        self.vx *= factor;
+        self.vy *= factor;
+    }
+
+    /// Slows the ball down to the given speed, keeping its direction. A slower ball keeps its
+    /// speed.
+    ///
+    /// # Parameters
+    /// - `max`: The top speed, in units per second.
+    pub fn cap_speed(&mut self, max: f64) {
+        let speed = self.vx.hypot(self.vy);
+        if speed > max {
+            self.slow_down(max / speed);
+        }
+    }
+
+    /// Rotates the ball's velocity by the given angle, keeping its speed.
+    ///
+    /// # Parameters
+    /// - `angle`: The angle in radians, counter-clockwise.
+    pub fn rotate(&mut self, angle: f64) {
+        let (sin, cos) = angle.sin_cos();
+        (self.vx, self.vy) = (self.vx * cos - self.vy * sin, self.vx * sin + self.vy * cos);
+    }
+
+    /// Turns the ball, keeping its speed, so that it moves vertically with at least the
+    /// minimum share of it. A horizontal ball is turned down.
+    fn keep_vertical_speed(&mut self) {
+        let speed = self.vx.hypot(self.vy);
+        let min = speed * self.min_vertical;
+        if self.vy.abs() >= min {
+            return;
+        }
+        let sign = if self.vy > 0. { 1. } else { -1. };
+        self.vy = sign * min;
+        self.vx = self.vx.signum() * (speed.powi(2) - min.powi(2)).max(0.).sqrt();
+    }
+
+    /// Turns the ball to the given angle, keeping its speed.
+    ///
+    /// # Parameters
+    /// - `angle`: The angle in radians from straight up, clockwise.
+    pub fn aim(&mut self, angle: f64) {
+        let speed = self.vx.hypot(self.vy);
+        let (sin, cos) = angle.sin_cos();
+        (self.vx, self.vy) = (speed * sin, speed * cos);
+    }
+
+    /// Predicts the path of the ball at its current velocity, bouncing off the sides and the
+    /// top of `bounds` and passing through anything inside them, e.g. the bricks.
+    ///
+    /// # Parameters
+    /// - `steps`: The number of positions to predict, [`PREDICTION_STEP`] apart.
+    /// - `bounds`: The area the ball bounces around in, e.g. between the walls.
+    ///
+    /// # Returns
+    /// The predicted positions of the ball's center, ending early if the ball falls out of the
+    /// bottom of `bounds`.
+    pub fn predict(&self, steps: usize, bounds: &Rectf64) -> Vec<(f64, f64)> {
+        let (mut x, mut y) = (self.x, self.y);
+        let (mut vx, mut vy) = (self.vx, self.vy);
+        let (min_x, max_x) = (bounds.left() + self.radius, bounds.right() - self.radius);
+        let max_y = bounds.top() - self.radius;
+        let mut path = Vec::with_capacity(steps);
+        for _ in 0..steps {
+            x += vx * PREDICTION_STEP;
+            y += vy * PREDICTION_STEP;
+            // Mirror the overshoot back inside the bounds.
+            if x < min_x {
+                x = 2. * min_x - x;
+                vx = vx.abs();
+            } else if x > max_x {
+                x = 2. * max_x - x;
+                vx = -vx.abs();
+            }
+            if y > max_y {
+                y = 2. * max_y - y;
+                vy = -vy.abs();
+            }
+            if y < bounds.bottom() {
+                break;
+            }
+            path.push((x, y));
+        }
+        path
+    }
+
+    /// Bounces two overlapping balls off each other elastically. The balls weigh the same, so
+    /// they exchange the components of their velocities along the line between their centers.
+    ///
+    /// # Parameters
+    /// - `other`: The other ball.
+    ///
+    /// # Returns
+    /// `true` if the balls collided, `false` if they don't overlap or already move apart.
+    pub fn collide_with(&mut self, other: &mut Ball) -> bool {
+        let (dx, dy) = (other.x - self.x, other.y - self.y);
+        let distance = dx.hypot(dy);
+        if distance >= self.radius + other.radius || distance == 0. {
+            return false;
+        }
+        let (nx, ny) = (dx / distance, dy / distance);
+        let closing = (self.vx - other.vx) * nx + (self.vy - other.vy) * ny;
+        if closing <= 0. {
+            return false;
+        }
+        (self.vx, self.vy) = (self.vx - closing * nx, self.vy - closing * ny);
+        (other.vx, other.vy) = (other.vx + closing * nx, other.vy + closing * ny);
+        true
+    }
+
     pub fn dsquared<EC: EllasticCollision>(&self, shape: &EC) -> f64 {
-        let area = shape.area();
-        let closest_x = f64::clamp(self.x, area.left(), area.right());
-        let closest_y = f64::clamp(self.y, area.bottom(), area.top());
-        let dx = self.x - closest_x;
-        let dy = self.y - closest_y;
+        Self::dsquared_at(self.x, self.y, shape)
+    }
+
+    /// Computes the squared distance from a point to the closest point of a shape.
+    fn dsquared_at<EC: EllasticCollision>(x: f64, y: f64, shape: &EC) -> f64 {
+        let area = shape.collision_area();
+        let closest_x = f64::clamp(x, area.left(), area.right());
+        let closest_y = f64::clamp(y, area.bottom(), area.top());
+        let dx = x - closest_x;
+        let dy = y - closest_y;
         dx.powi(2) + dy.powi(2)
     }
 
+    /// Predicts whether the ball will touch a shape after moving for the given time.
+    ///
+    /// # Parameters
+    /// - `shape`: The shape to check against.
+    /// - `dt`: The time delta to look ahead, in seconds.
+    ///
+    /// # Returns
+    /// `true` if the ball will overlap the shape at its projected position, `false` otherwise.
+    pub fn will_collide<EC: EllasticCollision>(&self, shape: &EC, dt: f64) -> bool {
+        let x = self.x + self.vx * dt;
+        let y = self.y + self.vy * dt;
+        Self::dsquared_at(x, y, shape) < self.radius.powi(2) && !shape.lets_through(self)
+    }
+
     pub fn collision<EC: EllasticCollision>(&mut self, shape: &EC) -> bool {
-        if self.dsquared(shape) < self.radius.powi(2) {
+        if self.dsquared(shape) < self.radius.powi(2) && !shape.lets_through(self) {
             #[cfg(feature = "debug")]
             tracing::debug!("The ball {self:?} collides with {shape:?}.");
             shape.collide(self);
+            self.keep_vertical_speed();
             true
         } else {
             false
@@ -141,22 +518,296 @@ pub trait EllasticCollision: std::fmt::Debug {
     /// `true` if a collision occurred, `false` otherwise.
     fn collide(&self, ball: &mut Ball);
     fn area(&self) -> Rectf64;
+
+    /// Returns the area the ball collides with, the shape's own area unless it's more
+    /// forgiving than it looks.
+    fn collision_area(&self) -> Rectf64 {
+        self.area()
+    }
+
+    /// Checks whether the given `Ball` passes through the shape without colliding.
+    ///
+    /// # Parameters
+    /// - `ball`: The ball to check.
+    ///
+    /// # Returns
+    /// `true` if the ball is let through, `false` otherwise.
+    fn lets_through(&self, _ball: &Ball) -> bool {
+        false
+    }
 }
 
+impl Ball {
+    /// Draws the ball with the given renderer in the given color mode.
+    ///
+    /// # Parameters
+    /// - `renderer`: The renderer to draw the ball with.
+    /// - `mode`: The color mode to draw in.
+    pub fn draw_in<R: GameRenderer>(&self, renderer: &mut R, mode: ColorMode) {
+        if self.motion_blur || self.on_fire {
+            // The streak fades towards where the ball came from, trailing further behind a
+            // ball on fire.
+            let length = if self.on_fire { FIRE_TRAIL } else { 1. };
+            let (x0, y0) = (
+                self.x - (self.x - self.previous.0) * length,
+                self.y - (self.y - self.previous.1) * length,
+            );
+            let (xm, ym) = ((x0 + self.x) / 2., (y0 + self.y) / 2.);
+            for (x1, y1, x2, y2, color) in [
+                (x0, y0, xm, ym, Color::DarkGray),
+                (xm, ym, self.x, self.y, Color::Red),
+            ] {
+                renderer.draw_line(x1, y1, x2, y2, mode.apply(color));
+            }
+        }
+        let color = if self.on_fire {
+            Color::Yellow
+        } else {
+            Color::LightRed
+        };
+        draw_glow(renderer, (self.x, self.y), self.radius, mode.apply(color));
+    }
+}
+
+/// Draws a ball's glow, nested circles shrinking towards its center, anywhere and at any
+/// size, e.g. for the ball icons of the lives left.
+///
+/// # Parameters
+/// - `renderer`: The renderer to draw the glow with.
+/// - `center`: The `(x, y)` coordinates of the center.
+/// - `radius`: The radius of the outermost circle.
+/// - `color`: The color of the circles.
+pub fn draw_glow<R: GameRenderer + ?Sized>(
+    renderer: &mut R,
+    center: (f64, f64),
+    radius: f64,
+    color: Color,
+) {
+    for k in (1..=10).map(|d| 1. / d as f64) {
+        renderer.draw_circle(center.0, center.1, radius * k, color);
+    }
+}
+
+#[cfg(feature = "tui")]
 impl Shape for Ball {
     /// Draws the ball on the given `Painter`.
     ///
     /// # Parameters
     /// - `painter`: The painter to draw the ball on.
     fn draw(&self, painter: &mut Painter) {
-        for k in (1..=10).map(|d| 1. / d as f64) {
-            Circle {
-                x: self.x,
-                y: self.y,
-                radius: self.radius * k,
-                color: Color::LightRed,
-            }
-            .draw(painter);
+        self.draw_in(painter, ColorMode::Full);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records the lines drawn, headlessly.
+    #[derive(Default)]
+    struct Recorder {
+        /// The ends of the lines.
+        lines: Vec<(f64, f64, f64, f64)>,
+    }
+
+    impl GameRenderer for Recorder {
+        fn draw_point(&mut self, _x: f64, _y: f64, _color: Color) {}
+
+        fn draw_line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, _color: Color) {
+            self.lines.push((x1, y1, x2, y2));
+        }
+
+        fn draw_circle(&mut self, _x: f64, _y: f64, _radius: f64, _color: Color) {}
+    }
+
+    #[test]
+    fn test_fire_trail() {
+        let trail = |on_fire| {
+            let mut ball = Ball::new(10., 10., 3., 40., 0.).motion_blur(true);
+            ball.set_on_fire(on_fire);
+            ball.mov(0.1);
+            let mut recorder = Recorder::default();
+            ball.draw_in(&mut recorder, ColorMode::Full);
+            let (x0, _, _, _) = recorder.lines[0];
+            let (_, _, x1, _) = *recorder.lines.last().unwrap();
+            x1 - x0
+        };
+        assert!((trail(false) - 4.).abs() < 1e-9);
+        assert!((trail(true) - 4. * FIRE_TRAIL).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_min_vertical_speed() {
+        let side = Rectf64 {
+            x: 0.,
+            y: 0.,
+            width: 2.,
+            height: 100.,
+        };
+        let walls = crate::walls::Walls::new(side.clone(), side.clone(), side, Color::White);
+        let mut ball = Ball::new(4., 50., 3., -40., 1.).min_vertical_speed(0.2);
+        let speed = 40f64.hypot(1.);
+        assert!(ball.collision(&walls.left));
+        let (vx, vy) = ball.velocity();
+        assert!(vx > 0.);
+        assert!((vy - 0.2 * speed).abs() < 1e-9);
+        assert!((vx.hypot(vy) - speed).abs() < 1e-9);
+
+        // A horizontal ball is turned down.
+        let mut ball = Ball::new(4., 50., 3., -40., 0.).min_vertical_speed(0.2);
+        assert!(ball.collision(&walls.left));
+        assert!((ball.velocity().1 + 8.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_predict() {
+        let bounds = Rectf64 {
+            x: 0.,
+            y: 0.,
+            width: 100.,
+            height: 50.,
+        };
+        // Straight up, back down from the top.
+        let ball = Ball::new(50., 40., 1., 0., 20.);
+        let path = ball.predict(8, &bounds);
+        assert_eq!(path.len(), 8);
+        assert!((path[3].1 - 48.).abs() < 1e-9);
+        assert!((path[4].1 - 48.).abs() < 1e-9);
+        assert!((path[7].1 - 42.).abs() < 1e-9);
+        assert!(path.iter().all(|&(x, _)| x == 50.));
+        // The ball isn't moved by the prediction.
+        assert_eq!(ball.position(), (50., 40.));
+
+        // Off the right side, mirrored.
+        let ball = Ball::new(95., 10., 1., 30., 0.);
+        let path = ball.predict(3, &bounds);
+        assert!((path[1].0 - 97.).abs() < 1e-9);
+        assert!((path[2].0 - 94.).abs() < 1e-9);
+
+        // Down out of the bottom, the path ends.
+        let ball = Ball::new(50., 2., 1., 0., -10.);
+        assert_eq!(ball.predict(10, &bounds).len(), 2);
+    }
+
+    #[test]
+    fn test_restitution() {
+        let mut ball = Ball::new(0., 0., 1., 4., 2.).restitution(0.5);
+        ball.bouncev();
+        assert_eq!((ball.vx, ball.vy), (4., -1.));
+        ball.reflect((-1., 0.));
+        assert_eq!((ball.vx, ball.vy), (-2., -1.));
+        // Rebounds keep the full speed.
+        ball.rebound((0., 1.));
+        assert_eq!((ball.vx, ball.vy), (-2., 1.));
+    }
+
+    #[test]
+    fn test_ease_in_ramps_to_full_speed() {
+        let mut ball = Ball::new(0., 0., 1., 30., 40.).ease_in(0.5);
+        assert_eq!(ball.speed(), 0.);
+
+        let dt = 0.05;
+        let mut last = 0.;
+        for _ in 0..20 {
+            let (x, y) = ball.position();
+            ball.mov(dt);
+            let (nx, ny) = ball.position();
+            let speed = (nx - x).hypot(ny - y) / dt;
+            assert!(speed >= last);
+            assert!(speed <= 50. + 1e-9);
+            last = speed;
         }
+        assert!((last - 50.).abs() < 1e-9);
+        assert!((ball.speed() - 50.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mov_remembers_the_previous_position() {
+        let mut ball = Ball::new(10., 20., 3., 30., -60.);
+        assert_eq!(ball.previous, (10., 20.));
+        ball.mov(0.5);
+        assert_eq!(ball.previous, (10., 20.));
+        assert_eq!(ball.position(), (25., -10.));
+        ball.mov(0.5);
+        assert_eq!(ball.previous, (25., -10.));
+    }
+
+    #[test]
+    fn test_reflect() {
+        // Off a floor and a ceiling.
+        let mut ball = Ball::new(0., 0., 1., 3., -4.);
+        ball.reflect((0., 1.));
+        assert_eq!(ball.velocity(), (3., 4.));
+        ball.reflect((0., -1.));
+        assert_eq!(ball.velocity(), (3., -4.));
+
+        // Off a wall on the right.
+        ball.reflect((-1., 0.));
+        assert_eq!(ball.velocity(), (-3., -4.));
+
+        // Off a 45° slope, turning the ball by a right angle.
+        let mut ball = Ball::new(0., 0., 1., -2., 0.);
+        let n = std::f64::consts::FRAC_1_SQRT_2;
+        ball.reflect((n, n));
+        let (vx, vy) = ball.velocity();
+        assert!(vx.abs() < 1e-9 && (vy - 2.).abs() < 1e-9);
+
+        // Moving away from the surface, the ball isn't affected.
+        ball.reflect((0., 1.));
+        assert_eq!(ball.velocity(), (vx, vy));
+    }
+
+    #[test]
+    fn test_contact_normal() {
+        let block = Block(Rectf64 {
+            x: 0.,
+            y: 0.,
+            width: 10.,
+            height: 4.,
+        });
+        assert_eq!(
+            Ball::new(5., -1., 2., 0., 0.).contact_normal(&block),
+            (0., -1.)
+        );
+        assert_eq!(
+            Ball::new(11., 2., 2., 0., 0.).contact_normal(&block),
+            (1., 0.)
+        );
+        let (nx, ny) = Ball::new(11., 5., 2., 0., 0.).contact_normal(&block);
+        assert!((nx - ny).abs() < 1e-9 && nx > 0.);
+        // The center inside the block is pushed out through the closest edge.
+        assert_eq!(
+            Ball::new(5., 3.5, 2., 0., 0.).contact_normal(&block),
+            (0., 1.)
+        );
+    }
+
+    #[derive(Debug)]
+    struct Block(Rectf64);
+
+    impl EllasticCollision for Block {
+        fn collide(&self, _ball: &mut Ball) {}
+
+        fn area(&self) -> Rectf64 {
+            self.0.clone()
+        }
+    }
+
+    #[test]
+    fn test_will_collide() {
+        let wall = Block(Rectf64 {
+            x: 100.,
+            y: 0.,
+            width: 2.,
+            height: 100.,
+        });
+        let ball = Ball::new(95., 50., 3., 10., 0.);
+        assert!(!ball.will_collide(&wall, 0.));
+        assert!(!ball.will_collide(&wall, 0.1));
+        assert!(ball.will_collide(&wall, 0.5));
+
+        // Moving away from the wall never collides.
+        let ball = Ball::new(95., 50., 3., -10., 0.);
+        assert!(!ball.will_collide(&wall, 0.5));
     }
 }
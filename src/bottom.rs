@@ -1,14 +1,19 @@
 use crate::ball::{Ball, EllasticCollision};
 use crate::rectf64::Rectf64;
+use crate::render::GameRenderer;
 use ratatui::style::Color;
-use ratatui::widgets::canvas::{Line, Painter, Shape};
+#[cfg(feature = "tui")]
+use ratatui::widgets::canvas::{Painter, Shape};
 
 /// Represents the bottom boundary of the game area.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bottom {
     /// The rectangular area representing the bottom boundary.
     area: Rectf64,
     color: Color,
+    /// Whether the ball bounces off the bottom instead of being lost.
+    bounces: bool,
 }
 
 impl Bottom {
@@ -20,34 +25,70 @@ impl Bottom {
     /// # Returns
     /// A new `Bottom` instance with the specified area.
     pub fn new(area: Rectf64, color: Color) -> Self {
-        Self { area, color }
+        Self {
+            area,
+            color,
+            bounces: false,
+        }
+    }
+
+    /// Makes the ball bounce off the bottom like off a wall instead of being lost.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to bounce the ball back.
+    ///
+    /// # Returns
+    /// The updated `Bottom`.
+    pub fn bouncing(mut self, enabled: bool) -> Self {
+        self.bounces = enabled;
+        self
+    }
+
+    /// Returns whether the ball bounces off the bottom instead of being lost.
+    pub fn bounces(&self) -> bool {
+        self.bounces
     }
 }
 
 impl EllasticCollision for Bottom {
-    /// Checks if the ball intersects with the bottom boundary.
+    /// Checks if the ball intersects with the bottom boundary, bouncing it back up if the
+    /// bottom bounces.
     ///
     /// # Parameters
     /// - `ball`: The ball to check for collision.
     ///
     /// # Returns
     /// `true` if the ball intersects with the bottom boundary, `false` otherwise.
-    fn collide(&self, _ball: &mut Ball) {}
+    fn collide(&self, ball: &mut Ball) {
+        if self.bounces {
+            ball.reflect((0., 1.));
+        }
+    }
 
     fn area(&self) -> Rectf64 {
         self.area.clone()
     }
 }
 
+impl Bottom {
+    /// Draws the bottom line with the given renderer.
+    ///
+    /// # Parameters
+    /// - `renderer`: The renderer to draw the bottom with.
+    pub fn render<R: GameRenderer>(&self, renderer: &mut R) {
+        renderer.draw_line(
+            self.area.left(),
+            self.area.top(),
+            self.area.right(),
+            self.area.bottom(),
+            self.color,
+        );
+    }
+}
+
+#[cfg(feature = "tui")]
 impl Shape for Bottom {
     fn draw(&self, painter: &mut Painter) {
-        Line {
-            x1: self.area.left(),
-            x2: self.area.right(),
-            y1: self.area.top(),
-            y2: self.area.bottom(),
-            color: self.color,
-        }
-        .draw(painter);
+        self.render(painter);
     }
 }
@@ -1,13 +1,103 @@
 use crate::ball::{Ball, EllasticCollision};
+use crate::color::ColorMode;
 use crate::rectf64::Rectf64;
+use crate::render::GameRenderer;
 use ratatui::style::Color;
-use ratatui::widgets::canvas::{Painter, Rectangle, Shape};
+#[cfg(feature = "tui")]
+use ratatui::widgets::canvas::{Painter, Shape};
+
+/// The deepest a brick can be split, keeping the number of bricks bounded.
+pub const MAX_SPLIT_DEPTH: u8 = 2;
+
+/// The default colors of the multi-hit bricks, from yellow with a single hit left, through
+/// orange, to red with three hits or more left.
+const DURABILITY_RAMP: [Color; 3] = [
+    Color::Rgb(255, 255, 85),
+    Color::Rgb(255, 165, 0),
+    Color::Rgb(255, 85, 85),
+];
+
+/// The width and height of a letter drawn by [`GameRenderer::draw_text`].
+const GLYPH_SIZE: f64 = 5.0;
+
+/// The smallest width and height of a brick with room for a health bar inside its outline.
+const HEALTH_BAR_MIN_SIZE: f64 = 3.0;
+
+/// How the bricks show how many hits they take.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BrickStyle {
+    /// The colors of the multi-hit bricks by the number of hits left, starting with a single
+    /// one. A brick with more hits left than there are colors takes the last one, and with no
+    /// colors at all it keeps its regular one.
+    pub durability_ramp: Vec<Color>,
+    /// Whether the multi-hit bricks show the number of hits they have left.
+    pub hp_digits: bool,
+    /// Whether the multi-hit bricks show a bar along their top, filled by the share of the
+    /// hits they have left.
+    pub health_bars: bool,
+}
+
+impl Default for BrickStyle {
+    fn default() -> Self {
+        Self {
+            durability_ramp: DURABILITY_RAMP.to_vec(),
+            hp_digits: false,
+            health_bars: false,
+        }
+    }
+}
+
+impl BrickStyle {
+    /// Picks the color of a multi-hit brick from the durability ramp.
+    ///
+    /// # Parameters
+    /// - `hp`: The number of hits the brick has left.
+    ///
+    /// # Returns
+    /// The color for `hp`, clamped to the ends of the ramp, or `None` if the ramp is empty.
+    pub fn durability_color(&self, hp: u8) -> Option<Color> {
+        let index = usize::from(hp.max(1) - 1);
+        self.durability_ramp
+            .get(index)
+            .or(self.durability_ramp.last())
+            .copied()
+    }
+}
+
+/// The diagonal of a sloped brick the ball is deflected by.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Slope {
+    /// From the bottom-left to the top-right corner, like `/`.
+    Rising,
+    /// From the top-left to the bottom-right corner, like `\`.
+    Falling,
+}
 
 /// Represents a brick with a rectangular area.
-#[derive(Debug, Default, Clone, PartialOrd, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Brick {
     /// The rectangular area occupied by the brick.
     area: Rectf64,
+    /// The brightness of the brick, `1.0` being its regular color.
+    glow: f64,
+    /// How many more times the brick splits in two instead of disappearing.
+    splits: u8,
+    /// Whether the ball passes through the brick, set for the halves of a split brick and for
+    /// a damaged brick until the ball leaves them.
+    ghost: bool,
+    /// Whether the brick is hidden in the fog until the ball comes close.
+    hidden: bool,
+    /// The diagonal deflecting the ball, if the brick is sloped.
+    slope: Option<Slope>,
+    /// The color the brick is drawn with instead of the regular one.
+    color: Option<Color>,
+    /// How many more hits the brick takes before it's destroyed.
+    hp: u8,
+    /// The hit points the brick started with.
+    full_hp: u8,
 }
 
 impl Brick {
@@ -19,14 +109,194 @@ impl Brick {
     /// # Returns
     /// A new `Brick` instance with the specified area.
     pub fn new(area: Rectf64) -> Self {
-        Self { area }
+        Self {
+            area,
+            glow: 1.,
+            splits: 0,
+            ghost: false,
+            hidden: false,
+            slope: None,
+            color: None,
+            hp: 1,
+            full_hp: 1,
+        }
+    }
+
+    /// Makes the brick take several hits before it's destroyed.
+    ///
+    /// # Parameters
+    /// - `hp`: The number of hits, at least one.
+    ///
+    /// # Returns
+    /// The updated `Brick`.
+    pub fn with_hp(mut self, hp: u8) -> Self {
+        self.hp = hp.max(1);
+        self.full_hp = self.hp;
+        self
+    }
+
+    /// Returns how many more hits the brick takes before it's destroyed.
+    pub fn hp(&self) -> u8 {
+        self.hp
+    }
+
+    /// Returns how many hits the brick took when it was new.
+    pub fn full_hp(&self) -> u8 {
+        self.full_hp
+    }
+
+    /// Takes a hit point for the ball hitting the brick. A brick that survives the hit lets
+    /// the ball through until it leaves, so it isn't hit again while the ball bounces off.
+    ///
+    /// # Returns
+    /// `true` if the brick is destroyed, `false` otherwise.
+    pub fn hit(&mut self) -> bool {
+        self.hp = self.hp.saturating_sub(1);
+        self.ghost = self.hp > 0;
+        self.hp == 0
+    }
+
+    /// Destroys the brick in a single hit whatever its hit points, e.g. hit by a ball on fire.
+    pub fn shatter(&mut self) {
+        self.hp = 0;
+        self.ghost = false;
+    }
+
+    /// Makes the brick split into two halves when destroyed.
+    ///
+    /// # Parameters
+    /// - `depth`: How many times the brick and its halves split, capped at
+    ///   [`MAX_SPLIT_DEPTH`].
+    ///
+    /// # Returns
+    /// The updated `Brick`.
+    pub fn splitting(mut self, depth: u8) -> Self {
+        self.splits = depth.min(MAX_SPLIT_DEPTH);
+        self
+    }
+
+    /// Returns the bricks left in place of the destroyed brick.
+    ///
+    /// # Returns
+    /// The left and right halves of a splitting brick, broken by a single hit, or nothing if
+    /// the brick doesn't split.
+    pub fn split(&self) -> Vec<Brick> {
+        if self.splits == 0 {
+            return vec![];
+        }
+        let width = self.area.width / 2.;
+        [self.area.x, self.area.x + width]
+            .into_iter()
+            .map(|x| Self {
+                area: Rectf64 {
+                    x,
+                    width,
+                    ..self.area.clone()
+                },
+                glow: self.glow,
+                splits: self.splits - 1,
+                ghost: true,
+                hidden: self.hidden,
+                slope: self.slope,
+                color: self.color,
+                hp: 1,
+                full_hp: 1,
+            })
+            .collect()
+    }
+
+    /// Makes the brick sloped: the ball is deflected by its diagonal at 45°, like off a
+    /// bumper, instead of by its sides.
+    ///
+    /// # Parameters
+    /// - `slope`: The diagonal deflecting the ball.
+    ///
+    /// # Returns
+    /// The updated `Brick`.
+    pub fn sloped(mut self, slope: Slope) -> Self {
+        self.slope = Some(slope);
+        self
+    }
+
+    /// Draws the brick in the given color instead of the regular one. Purely cosmetic.
+    ///
+    /// # Parameters
+    /// - `color`: The color of the brick.
+    ///
+    /// # Returns
+    /// The updated `Brick`.
+    pub fn colored(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Returns the color the brick is drawn with, if it isn't the regular one.
+    #[cfg(test)]
+    pub fn color(&self) -> Option<Color> {
+        self.color
+    }
+
+    /// Hides the brick in the fog until the ball comes close, see [`Brick::reveal_near`].
+    ///
+    /// # Parameters
+    /// - `hidden`: `true` to hide the brick.
+    ///
+    /// # Returns
+    /// The updated `Brick`.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Returns whether the brick is hidden in the fog.
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    /// Reveals the brick if the ball is within `radius` of it.
+    ///
+    /// # Parameters
+    /// - `ball`: The ball lighting up the fog.
+    /// - `radius`: How far from its edge, besides touching it, the ball reveals the brick.
+    pub fn reveal_near(&mut self, ball: &Ball, radius: f64) {
+        if self.hidden && ball.dsquared(self) <= (ball.radius() + radius).powi(2) {
+            self.hidden = false;
+        }
+    }
+
+    /// Makes the halves of a split brick, or a damaged brick, solid once the ball no longer
+    /// touches them, so the ball doesn't destroy them right away.
+    ///
+    /// # Parameters
+    /// - `ball`: The ball that split the brick.
+    pub fn settle(&mut self, ball: &Ball) {
+        if self.ghost && ball.dsquared(self) >= ball.radius().powi(2) {
+            self.ghost = false;
+        }
+    }
+
+    /// Sets the brightness the brick is drawn with.
+    ///
+    /// # Parameters
+    /// - `glow`: The brightness, `1.0` being the regular color.
+    pub fn set_glow(&mut self, glow: f64) {
+        self.glow = glow.clamp(0., 1.);
+    }
+
+    /// Moves the brick down.
+    ///
+    /// # Parameters
+    /// - `dy`: The distance to move the brick by.
+    pub fn shift_down(&mut self, dy: f64) {
+        self.area.y -= dy;
     }
 }
 
 impl EllasticCollision for Brick {
     /// Checks for and handles a collision with the given `Ball`.
     ///
-    /// If the ball intersects with the brick, the ball's vertical velocity is reversed.
+    /// If the ball intersects with the brick, it's reflected off the side or the corner it
+    /// touches, or off the diagonal of a sloped brick.
     ///
     /// # Parameters
     /// - `ball`: The ball to check for collision.
@@ -34,27 +304,378 @@ impl EllasticCollision for Brick {
     /// # Returns
     /// `true` if a collision occurred, `false` otherwise.
     fn collide(&self, ball: &mut Ball) {
-        ball.bouncev();
+        let Some(slope) = self.slope else {
+            ball.reflect(ball.contact_normal(self));
+            return;
+        };
+        let n = std::f64::consts::FRAC_1_SQRT_2;
+        let normal = match slope {
+            Slope::Rising => (-n, n),
+            Slope::Falling => (n, n),
+        };
+        // Use the side of the diagonal the ball is on.
+        let (x, y) = ball.position();
+        let (cx, cy) = (
+            self.area.x + self.area.width / 2.,
+            self.area.y + self.area.height / 2.,
+        );
+        if (x - cx) * normal.0 + (y - cy) * normal.1 >= 0. {
+            ball.reflect(normal);
+        } else {
+            ball.reflect((-normal.0, -normal.1));
+        }
     }
 
     fn area(&self) -> Rectf64 {
         self.area.clone()
     }
+
+    fn lets_through(&self, _ball: &Ball) -> bool {
+        self.ghost
+    }
 }
 
-impl Shape for Brick {
-    /// Draws the brick on the given `Painter`.
+impl Brick {
+    /// Draws the brick with the given renderer in the given color mode.
     ///
     /// # Parameters
-    /// - `painter`: The painter to draw the brick on.
-    fn draw(&self, painter: &mut Painter) {
-        Rectangle {
+    /// - `renderer`: The renderer to draw the brick with.
+    /// - `mode`: The color mode to draw in.
+    /// - `style`: How the brick shows the hits it takes.
+    pub fn draw_in<R: GameRenderer>(&self, renderer: &mut R, mode: ColorMode, style: &BrickStyle) {
+        if let Some(slope) = self.slope {
+            let (y1, y2) = match slope {
+                Slope::Rising => (self.area.bottom(), self.area.top()),
+                Slope::Falling => (self.area.top(), self.area.bottom()),
+            };
+            let color = if self.hidden {
+                Color::DarkGray
+            } else {
+                self.color.unwrap_or(Color::LightMagenta)
+            };
+            renderer.draw_line(
+                self.area.left(),
+                y1,
+                self.area.right(),
+                y2,
+                mode.apply(color),
+            );
+            return;
+        }
+        let outline = Rectf64 {
             x: self.area.x + 1.,
             y: self.area.y + 1.,
             height: self.area.height - 1.,
             width: self.area.width - 1.,
-            color: Color::LightYellow,
+        };
+        // The multi-hit bricks are colored by the hits they have left.
+        let base = if self.full_hp > 1 {
+            style.durability_color(self.hp).or(self.color)
+        } else {
+            self.color
+        };
+        let color = if self.hidden {
+            Color::DarkGray
+        } else if self.glow < 1. {
+            let scale = |c: u8| (c as f64 * self.glow) as u8;
+            match base.unwrap_or(Color::Rgb(255, 255, 85)) {
+                Color::Rgb(r, g, b) => Color::Rgb(scale(r), scale(g), scale(b)),
+                color => color,
+            }
+        } else {
+            base.unwrap_or(Color::LightYellow)
+        };
+        renderer.draw_rect(&outline, mode.apply(color));
+        if style.hp_digits && self.full_hp > 1 && !self.hidden {
+            self.draw_hp(renderer, mode.apply(Color::White));
+        }
+        if style.health_bars && self.full_hp > 1 && !self.hidden {
+            self.draw_health_bar(renderer, &outline, mode.apply(Color::LightGreen));
+        }
+    }
+
+    /// Draws a bar just below the top of the brick's outline, filled by the share of the hits
+    /// the brick has left, unless the brick is too small to fit it.
+    ///
+    /// # Parameters
+    /// - `renderer`: The renderer to draw the bar with.
+    /// - `outline`: The outline of the brick.
+    /// - `color`: The color of the bar.
+    fn draw_health_bar<R: GameRenderer>(&self, renderer: &mut R, outline: &Rectf64, color: Color) {
+        if self.area.width < HEALTH_BAR_MIN_SIZE || self.area.height < HEALTH_BAR_MIN_SIZE {
+            return;
+        }
+        let filled = outline.width * f64::from(self.hp) / f64::from(self.full_hp);
+        let y = outline.top() - 1.;
+        renderer.draw_line(outline.left(), y, outline.left() + filled, y, color);
+    }
+
+    /// Draws the number of hits the brick has left in its middle, unless the brick is too
+    /// small to fit it.
+    ///
+    /// # Parameters
+    /// - `renderer`: The renderer to draw the number with.
+    /// - `color`: The color of the number.
+    fn draw_hp<R: GameRenderer>(&self, renderer: &mut R, color: Color) {
+        let text = self.hp.to_string();
+        let width = text.len() as f64 * GLYPH_SIZE;
+        if self.area.width < width || self.area.height < GLYPH_SIZE {
+            return;
+        }
+        let x = self.area.x + (self.area.width - width) / 2.;
+        let y = self.area.y + (self.area.height - GLYPH_SIZE) / 2.;
+        renderer.draw_text(&text, (x, y), GLYPH_SIZE, color);
+    }
+}
+
+#[cfg(feature = "tui")]
+impl Shape for Brick {
+    /// Draws the brick on the given `Painter`.
+    ///
+    /// # Parameters
+    /// - `painter`: The painter to draw the brick on.
+    fn draw(&self, painter: &mut Painter) {
+        self.draw_in(painter, ColorMode::Full, &BrickStyle::default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records what's drawn instead of drawing anything.
+    #[derive(Default)]
+    struct Recorder {
+        /// The colors of the outlines.
+        outlines: Vec<Color>,
+        /// The texts and where they start.
+        texts: Vec<(String, (f64, f64))>,
+        /// The ends of the lines.
+        lines: Vec<(f64, f64, f64, f64)>,
+    }
+
+    impl GameRenderer for Recorder {
+        fn draw_point(&mut self, _x: f64, _y: f64, _color: Color) {}
+
+        fn draw_line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, _color: Color) {
+            self.lines.push((x1, y1, x2, y2));
+        }
+
+        fn draw_circle(&mut self, _x: f64, _y: f64, _radius: f64, _color: Color) {}
+
+        fn draw_rect(&mut self, _area: &Rectf64, color: Color) {
+            self.outlines.push(color);
+        }
+
+        fn draw_text(&mut self, text: &str, position: (f64, f64), _spacing: f64, _color: Color) {
+            self.texts.push((text.to_string(), position));
         }
-        .draw(painter);
+    }
+
+    #[test]
+    fn test_split() {
+        let brick = Brick::new(Rectf64 {
+            x: 10.,
+            y: 20.,
+            width: 14.,
+            height: 5.,
+        })
+        .splitting(5);
+        assert_eq!(brick.splits, MAX_SPLIT_DEPTH);
+
+        let halves = brick.split();
+        assert_eq!(halves.len(), 2);
+        assert_eq!(halves[0].area().x, 10.);
+        assert_eq!(halves[1].area().x, 17.);
+        assert!(halves.iter().all(|half| half.area().width == 7.));
+        assert!(halves.iter().all(|half| half.area().height == 5.));
+
+        let quarters = halves[0].split();
+        assert_eq!(quarters.len(), 2);
+        assert!(quarters[0].split().is_empty());
+    }
+
+    #[test]
+    fn test_multi_hit_brick() {
+        let mut brick = Brick::new(Rectf64 {
+            x: 10.,
+            y: 20.,
+            width: 14.,
+            height: 5.,
+        })
+        .with_hp(3);
+        let mut ball = Ball::new(17., 18., 3., 0., 30.);
+        assert!(ball.collision(&brick));
+        assert!(!brick.hit());
+        assert_eq!(brick.hp(), 2);
+        assert_eq!(brick.full_hp - brick.hp, 1);
+
+        // The ball bouncing off doesn't hit the brick again.
+        assert!(!ball.collision(&brick));
+        ball.mov(1.);
+        brick.settle(&ball);
+        assert!(!brick.hit());
+        assert!(brick.hit());
+        assert_eq!(brick.hp(), 0);
+    }
+
+    #[test]
+    fn test_durability_ramp() {
+        let style = BrickStyle {
+            durability_ramp: vec![Color::Yellow, Color::Rgb(255, 165, 0), Color::Red],
+            ..BrickStyle::default()
+        };
+        let area = Rectf64 {
+            x: 10.,
+            y: 20.,
+            width: 14.,
+            height: 5.,
+        };
+        // A brick with more hits left than there are colors takes the last one.
+        let mut brick = Brick::new(area.clone()).with_hp(4);
+        let mut recorder = Recorder::default();
+        for _ in 0..4 {
+            brick.draw_in(&mut recorder, ColorMode::Full, &style);
+            brick.hit();
+        }
+        assert_eq!(
+            recorder.outlines,
+            [
+                Color::Red,
+                Color::Red,
+                Color::Rgb(255, 165, 0),
+                Color::Yellow
+            ]
+        );
+
+        // The single-hit bricks keep their regular color.
+        let mut recorder = Recorder::default();
+        Brick::new(area).draw_in(&mut recorder, ColorMode::Full, &style);
+        assert_eq!(recorder.outlines, [Color::LightYellow]);
+    }
+
+    #[test]
+    fn test_hp_digits() {
+        let style = BrickStyle {
+            hp_digits: true,
+            ..BrickStyle::default()
+        };
+        let area = Rectf64 {
+            x: 10.,
+            y: 20.,
+            width: 14.,
+            height: 5.,
+        };
+        let mut brick = Brick::new(area.clone()).with_hp(3);
+        let mut recorder = Recorder::default();
+        for _ in 0..3 {
+            brick.draw_in(&mut recorder, ColorMode::Full, &style);
+            brick.hit();
+        }
+        let digits: Vec<_> = recorder
+            .texts
+            .iter()
+            .map(|(text, _)| text.as_str())
+            .collect();
+        assert_eq!(digits, ["3", "2", "1"]);
+        // The digit is centered on the brick.
+        assert_eq!(recorder.texts[0].1, (14.5, 20.));
+
+        // A single-hit brick has no digit, and neither has one too small to fit it.
+        let mut recorder = Recorder::default();
+        Brick::new(area.clone()).draw_in(&mut recorder, ColorMode::Full, &style);
+        Brick::new(Rectf64 { width: 4., ..area })
+            .with_hp(3)
+            .draw_in(&mut recorder, ColorMode::Full, &style);
+        assert!(recorder.texts.is_empty());
+    }
+
+    #[test]
+    fn test_health_bars() {
+        let style = BrickStyle {
+            health_bars: true,
+            ..BrickStyle::default()
+        };
+        let area = Rectf64 {
+            x: 10.,
+            y: 20.,
+            width: 14.,
+            height: 5.,
+        };
+        let mut brick = Brick::new(area.clone()).with_hp(4);
+        let mut recorder = Recorder::default();
+        for _ in 0..4 {
+            brick.draw_in(&mut recorder, ColorMode::Full, &style);
+            brick.hit();
+        }
+        // The bar runs along the top inside the outline, shrinking with every hit.
+        let lengths: Vec<_> = recorder
+            .lines
+            .iter()
+            .map(|(x1, _, x2, _)| x2 - x1)
+            .collect();
+        assert_eq!(lengths, [13., 9.75, 6.5, 3.25]);
+        assert!(recorder
+            .lines
+            .iter()
+            .all(|&(x1, y1, _, y2)| x1 == 11. && y1 == 24. && y2 == 24.));
+
+        // A brick too small for the bar has none.
+        let mut recorder = Recorder::default();
+        Brick::new(Rectf64 { height: 2., ..area })
+            .with_hp(4)
+            .draw_in(&mut recorder, ColorMode::Full, &style);
+        assert!(recorder.lines.is_empty());
+    }
+
+    #[test]
+    fn test_side_and_corner_hits() {
+        let brick = Brick::new(Rectf64 {
+            x: 10.,
+            y: 20.,
+            width: 14.,
+            height: 5.,
+        });
+
+        // Striking the left side only turns the ball back horizontally.
+        let mut ball = Ball::new(8., 22., 3., 30., 10.);
+        assert!(ball.collision(&brick));
+        assert_eq!(ball.velocity(), (-30., 10.));
+
+        // And striking a corner head-on turns it back along both axes.
+        let mut ball = Ball::new(8., 18., 3., 30., 30.);
+        assert!(ball.collision(&brick));
+        let (vx, vy) = ball.velocity();
+        assert!((vx + 30.).abs() < 1e-9 && (vy + 30.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_slope_deflects_sideways() {
+        let brick = Brick::new(Rectf64 {
+            x: 10.,
+            y: 20.,
+            width: 10.,
+            height: 10.,
+        })
+        .sloped(Slope::Rising);
+
+        // Hitting `/` from below turns the ball to the right.
+        let mut ball = Ball::new(16., 22., 3., 0., 30.);
+        assert!(ball.collision(&brick));
+        let (vx, vy) = ball.velocity();
+        assert!((vx - 30.).abs() < 1e-9 && vy.abs() < 1e-9);
+
+        // And hitting it from above turns it to the left.
+        let mut ball = Ball::new(14., 28., 3., 0., -30.);
+        assert!(ball.collision(&brick));
+        let (vx, vy) = ball.velocity();
+        assert!((vx + 30.).abs() < 1e-9 && vy.abs() < 1e-9);
+
+        // `\` turns the ball rising from below to the left.
+        let brick = brick.sloped(Slope::Falling);
+        let mut ball = Ball::new(14., 22., 3., 0., 30.);
+        assert!(ball.collision(&brick));
+        let (vx, vy) = ball.velocity();
+        assert!((vx + 30.).abs() < 1e-9 && vy.abs() < 1e-9);
     }
 }
@@ -0,0 +1,44 @@
+use ratatui::style::Color;
+
+/// How the game is colored.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorMode {
+    /// The regular colors.
+    #[default]
+    Full,
+    /// No colors at all, the terminal's default one is used for everything. The ball, the
+    /// paddle and the bricks are told apart by their shapes only.
+    Monochrome,
+}
+
+impl ColorMode {
+    /// Picks the color mode supported by the terminal.
+    ///
+    /// # Returns
+    /// [`ColorMode::Monochrome`] if the `NO_COLOR` environment variable is set or the terminal
+    /// is a dumb one, [`ColorMode::Full`] otherwise.
+    pub fn detect() -> Self {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        let dumb = std::env::var("TERM").is_ok_and(|term| term == "dumb");
+        if no_color || dumb {
+            Self::Monochrome
+        } else {
+            Self::Full
+        }
+    }
+
+    /// Returns the color to draw with in this mode.
+    ///
+    /// # Parameters
+    /// - `color`: The regular color.
+    ///
+    /// # Returns
+    /// `color` itself, or [`Color::Reset`] in the monochrome mode.
+    pub fn apply(self, color: Color) -> Color {
+        match self {
+            Self::Full => color,
+            Self::Monochrome => Color::Reset,
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier, Style};
+use std::fmt::Write;
+
+/// Serializes a rendered buffer into text with ANSI escape sequences for its colors.
+///
+/// # Parameters
+/// - `buffer`: The buffer to serialize.
+///
+/// # Returns
+/// The contents of the buffer, one line per row, each line ending with a style reset.
+pub fn to_ansi(buffer: &Buffer) -> String {
+    let mut out = String::new();
+    for y in buffer.area.top()..buffer.area.bottom() {
+        let mut style = None;
+        for x in buffer.area.left()..buffer.area.right() {
+            let cell = buffer.get(x, y);
+            if cell.skip {
+                continue;
+            }
+            let cell_style = cell.style();
+            if style != Some(cell_style) {
+                out.push_str(&sgr(cell_style));
+                style = Some(cell_style);
+            }
+            out.push_str(cell.symbol());
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// Builds the Select Graphic Rendition sequence for a style.
+fn sgr(style: Style) -> String {
+    let mut codes = vec!["0".to_string()];
+    let modifiers = style.add_modifier - style.sub_modifier;
+    for (modifier, code) in [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+    ] {
+        if modifiers.contains(modifier) {
+            codes.push(code.to_string());
+        }
+    }
+    codes.push(color_code(style.fg.unwrap_or(Color::Reset), false));
+    codes.push(color_code(style.bg.unwrap_or(Color::Reset), true));
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+/// Returns the SGR parameters selecting a foreground or background color.
+fn color_code(color: Color, background: bool) -> String {
+    let base = if background { 40 } else { 30 };
+    let named = |index: u8| {
+        if index < 8 {
+            (base + index).to_string()
+        } else {
+            (base + 60 + index - 8).to_string()
+        }
+    };
+    match color {
+        Color::Reset => (base + 9).to_string(),
+        Color::Black => named(0),
+        Color::Red => named(1),
+        Color::Green => named(2),
+        Color::Yellow => named(3),
+        Color::Blue => named(4),
+        Color::Magenta => named(5),
+        Color::Cyan => named(6),
+        Color::Gray => named(7),
+        Color::DarkGray => named(8),
+        Color::LightRed => named(9),
+        Color::LightGreen => named(10),
+        Color::LightYellow => named(11),
+        Color::LightBlue => named(12),
+        Color::LightMagenta => named(13),
+        Color::LightCyan => named(14),
+        Color::White => named(15),
+        Color::Indexed(index) => {
+            let mut code = String::new();
+            let _ = write!(code, "{};5;{index}", base + 8);
+            code
+        }
+        Color::Rgb(r, g, b) => {
+            let mut code = String::new();
+            let _ = write!(code, "{};2;{r};{g};{b}", base + 8);
+            code
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::prelude::Rect;
+
+    #[test]
+    fn test_to_ansi() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 2));
+        buffer.get_mut(1, 0).set_char('x').set_fg(Color::LightRed);
+        buffer
+            .get_mut(2, 1)
+            .set_char('#')
+            .set_fg(Color::Rgb(1, 2, 3))
+            .set_bg(Color::Blue);
+
+        assert_eq!(
+            to_ansi(&buffer),
+            "\x1b[0;39;49m \x1b[0;91;49mx\x1b[0;39;49m \x1b[0m\n\
+             \x1b[0;39;49m  \x1b[0;38;2;1;2;3;44m#\x1b[0m\n"
+        );
+    }
+}
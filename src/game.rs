@@ -1,28 +1,150 @@
-use crate::ball::Ball;
+use crate::ball::{self, Ball, EllasticCollision};
 use crate::bottom::Bottom;
-use crate::brick::Brick;
+use crate::brick::{Brick, BrickStyle};
+use crate::color::ColorMode;
 use crate::letters::Word;
-use crate::paddle::{Direction, Paddle};
+use crate::levels::{self, Level};
+use crate::paddle::{self, Direction, Paddle};
+use crate::powerup::{PowerUp, PowerUpKind};
 use crate::rectf64::Rectf64;
+use crate::render::GameRenderer;
 use crate::walls::Walls;
-use rand::{seq::SliceRandom, thread_rng};
+use anyhow::Context;
+use rand::rngs::StdRng;
+use rand::{seq::SliceRandom, Rng, SeedableRng};
 use ratatui::style::Color;
+#[cfg(feature = "tui")]
 use ratatui::widgets::canvas::{Painter, Shape};
+#[cfg(feature = "serde")]
+use std::path::Path;
+use std::path::PathBuf;
 
-/// Width of a brick.
+/// Default width of a brick.
 const BRICK_WIDTH: f64 = 14.0;
 
-/// Height of a brick.
+/// Default height of a brick.
 const BRICK_HEIGHT: f64 = 5.0;
 
-/// Width of the wall.
+/// The colors bricks are randomly drawn with, if enabled.
+const BRICK_PALETTE: [Color; 6] = [
+    Color::Rgb(255, 255, 85),
+    Color::Rgb(255, 85, 85),
+    Color::Rgb(85, 255, 85),
+    Color::Rgb(85, 170, 255),
+    Color::Rgb(255, 170, 0),
+    Color::Rgb(200, 120, 255),
+];
+
+/// Default top speed of the ball sped up by broken bricks, as a multiple of its initial speed.
+const MAX_SPEED_FACTOR: f64 = 3.0;
+
+/// Radius of the ball.
+const BALL_RADIUS: f64 = 3.0;
+
+/// Default width of the side walls.
 const WALL_W: f64 = 2.0;
 
-/// Height of the wall.
+/// Default height of the top wall.
 const WALL_H: f64 = 2.0;
 
+/// Hits this far from the paddle's center, as a share of its half-width, earn a bonus.
+const EDGE_ZONE: f64 = 0.75;
+
+/// Points awarded for catching the ball with the edge of the paddle.
+const EDGE_BONUS: usize = 2;
+
+/// For how long, in seconds, a score popup stays on screen.
+const POPUP_TTL: f64 = 1.0;
+
+/// Duration, in seconds, of a single brick pulse while the game is idle.
+const PULSE_PERIOD: f64 = 2.0;
+
+/// The angle, in radians below the horizon, a stalling ball is nudged down at.
+const NUDGE_ANGLE: f64 = std::f64::consts::FRAC_PI_4;
+
+/// The share of the time limit left at which the countdown turns yellow.
+const TIME_WARNING: f64 = 1. / 6.;
+
+/// The share of the time limit left at which the countdown turns red.
+const TIME_ALERT: f64 = 1. / 20.;
+
+/// Default number of balls the player can lose before the game is over.
+const LIVES: u8 = 3;
+
+/// The number of bricks every level adds to the one before it.
+const LEVEL_BRICKS: u16 = 10;
+
+/// The factor the ball's speed grows by with every level.
+const LEVEL_SPEEDUP: f64 = 1.1;
+
+/// The factor a caught [`PowerUpKind::WidePaddle`] widens the paddle by.
+const WIDE_PADDLE: f64 = 1.5;
+
+/// The factor a caught [`PowerUpKind::NarrowPaddle`] narrows the paddle by.
+const NARROW_PADDLE: f64 = 0.6;
+
+/// Default duration, in seconds, a caught power-up resizes the paddle for.
+const POWERUP_DURATION: f64 = 10.0;
+
+/// Default most balls in play at once.
+const MAX_BALLS: usize = 8;
+
+/// The angle, in radians, the clone of a vertical ball split by [`PowerUpKind::MultiBall`]
+/// is turned by, as mirroring it would overlap the two balls.
+const SPLIT_ANGLE: f64 = std::f64::consts::FRAC_PI_6;
+
+/// The largest angle, in radians, the two halves of a ball split by a wall bounce are turned
+/// apart by.
+const WALL_SPLIT_SPREAD: f64 = std::f64::consts::FRAC_PI_8;
+
+/// The share of its width the paddle gains in mercy mode for every life lost on a level.
+const MERCY_WIDENING: f64 = 0.15;
+
+/// The widest the paddle grows in mercy mode, as a factor of its original width.
+const MERCY_MAX_WIDTH: f64 = 1.6;
+
+/// The radius of a ball icon showing a life left.
+const LIFE_ICON_RADIUS: f64 = 2.0;
+
+/// The distance between the centers of two ball icons, leaving a gap that stays visible at a
+/// low resolution.
+const LIFE_ICON_SPACING: f64 = 8.0;
+
+/// The most ball icons shown, more lives than that are shown as a number.
+const MAX_LIFE_ICONS: u8 = 10;
+
+/// The time, in seconds, between the demo's paddle moves, about as often as a held key
+/// repeats.
+const DEMO_MOVE_INTERVAL: f64 = 1. / 30.;
+
+/// The share of its speed the ball keeps after a caught [`PowerUpKind::SlowBall`].
+const SLOW_BALL: f64 = 0.7;
+
+/// How long, in seconds, the banner announcing a new level is shown.
+const LEVEL_BANNER: f64 = 1.0;
+
+/// Default duration, in seconds, of the animation played when the ball is lost.
+const DEATH_ANIMATION: f64 = 1.0;
+
+/// Interval, in seconds, between two brick rows descending in invasion mode.
+const INVASION_INTERVAL: f64 = 8.0;
+
+/// How far, in game units, the ball reveals the bricks hidden in the fog.
+const REVEAL_RADIUS: f64 = 20.0;
+
+/// The number of dots of the aim line drawn from the ball resting on the paddle.
+const AIM_LINE_DOTS: usize = 20;
+
+/// Default upper bound for a single simulation step, in seconds.
+const MAX_FRAME_DELTA: f64 = 0.1;
+
+/// Number of recent effects kept for the debug overlay.
+#[cfg(feature = "debug")]
+const EFFECT_LOG_SIZE: usize = 32;
+
 /// Represents the state of the game.
 #[derive(Debug, Default, PartialOrd, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameState {
     /// The game is running.
     #[default]
@@ -31,6 +153,96 @@ pub enum GameState {
     Lost,
     /// The player has won the game.
     Won,
+    /// The time limit of the game ran out.
+    TimeUp,
+}
+
+/// Something that happened in the simulation, kept for debugging.
+#[cfg(feature = "debug")]
+#[derive(Debug, PartialEq, Clone)]
+pub enum GameEffect {
+    /// The ball bounced off a wall.
+    WallBounce,
+    /// The ball bounced off the paddle at the given offset from its center, in `[-1, 1]`.
+    PaddleBounce { offset: f64 },
+    /// The ball broke the given number of bricks.
+    BrickBreak { count: usize },
+    /// The ball was lost.
+    BallLost,
+}
+
+#[cfg(feature = "debug")]
+impl std::fmt::Display for GameEffect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameEffect::WallBounce => write!(f, "wall bounce"),
+            GameEffect::PaddleBounce { offset } => write!(f, "paddle bounce {offset:+.2}"),
+            GameEffect::BrickBreak { count } => write!(f, "broke {count} brick(s)"),
+            GameEffect::BallLost => write!(f, "ball lost"),
+        }
+    }
+}
+
+/// The direction the ball is launched in.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "tui", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InitialDir {
+    /// Up and to the left.
+    UpLeft,
+    /// Up and to the right.
+    #[default]
+    UpRight,
+    /// Either of the two, picked at random.
+    Random,
+}
+
+/// How the lives left are shown.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "tui", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LivesDisplay {
+    /// As a number.
+    #[default]
+    Number,
+    /// As a row of small balls in the top-right corner, like in the arcade.
+    Icons,
+}
+
+/// What the score of a broken brick is computed from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrickBreak {
+    /// The row of the brick, counted in brick heights from the top of the playfield.
+    pub row: usize,
+    /// The number of bricks broken since the ball last bounced off the paddle, this one
+    /// included.
+    pub combo: usize,
+    /// The speed of the ball, in units per second.
+    pub ball_speed: f64,
+    /// The hits the brick still takes, zero as it's destroyed.
+    pub hp: u8,
+    /// The hits it took to destroy the brick, one for a regular brick.
+    pub full_hp: u8,
+}
+
+/// Computes the points awarded for breaking a brick.
+pub type Scoring = fn(&BrickBreak) -> usize;
+
+/// The default scoring: every brick is worth a single point.
+fn one_point(_: &BrickBreak) -> usize {
+    1
+}
+
+/// A scoring rewarding streaks: every brick is worth as many points as the bricks broken
+/// since the ball last bounced off the paddle, this one included.
+pub fn combo_points(brick: &BrickBreak) -> usize {
+    brick.combo
+}
+
+/// Returns the default scoring, for the games resumed without their scoring.
+#[cfg(feature = "serde")]
+fn default_scoring() -> Scoring {
+    one_point
 }
 
 /// Represents an event in the game.
@@ -38,14 +250,19 @@ pub enum GameState {
 pub enum GameEvent {
     /// Event to move the paddle in a specified direction.
     MovePad { direction: Direction },
+    /// Event to move the paddle's center to an absolute x-coordinate, e.g. following the mouse.
+    MovePadTo { x: f64 },
     #[cfg(feature = "debug")]
     MoveBallManual { direction: Direction },
-    /// Event to update the game state with a time delta.
-    Tick,
+    /// Event to launch the ball resting on the paddle, see [`GameOptions::sticky_launch`].
+    Launch,
+    /// Event to update the game state with a time delta, in seconds.
+    Tick { dt: f64 },
 }
 
 /// Represents the options for configuring the game.
-#[derive(Default, Clone, Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameOptions {
     /// The number of bricks in the game.
     brick_count: u16,
@@ -56,6 +273,188 @@ pub struct GameOptions {
     /// The rectangular area defining the game space.
     area: Rectf64,
     ball_speed: f64,
+    /// Horizontal ranges where the top wall is open.
+    top_wall_gaps: Vec<(f64, f64)>,
+    /// The longest time step, in seconds, the simulation is allowed to take at once.
+    max_frame_delta: f64,
+    /// The share of the ball's velocity kept after a bounce.
+    restitution: f64,
+    /// Text drawn faintly behind the bricks.
+    background: Option<String>,
+    /// The share of the paddle around its center that bounces the ball without spin.
+    paddle_deadzone: f64,
+    /// Whether the bricks pulse while the game is idle.
+    brick_pulse: bool,
+    /// The largest random deflection, in radians, applied when the ball bounces off a brick.
+    bounce_jitter: f64,
+    /// The `(seed, difficulty)` of a generated brick layout.
+    generator: Option<(u64, u8)>,
+    /// The file a fixed brick layout is read from.
+    level_file: Option<PathBuf>,
+    /// The fixed brick layout to use instead of reading it from `level_file`.
+    level_layout: Option<String>,
+    /// The duration, in seconds, of the animation played when the ball is lost.
+    death_animation: f64,
+    /// How strongly the angle of impact turns the paddle's movement into spin.
+    paddle_angular_factor: f64,
+    /// The mass of the paddle relative to the ball.
+    paddle_mass_factor: f64,
+    /// The friction between the ball and the paddle.
+    paddle_friction: f64,
+    /// Whether new rows of bricks keep descending towards the paddle.
+    invasion_mode: bool,
+    /// How many times the bricks split in two before disappearing.
+    split_depth: u8,
+    /// The direction the ball is launched in.
+    initial_direction: InitialDir,
+    /// The longest time, in seconds, the game can be played for.
+    time_limit: Option<f64>,
+    /// Computes the points awarded for breaking a brick. A function can't be saved, so a
+    /// resumed game falls back to the default scoring.
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_scoring"))]
+    scoring: Scoring,
+    /// Whether the paddle highlights where the ball last hit it.
+    paddle_tilt: bool,
+    /// Whether the paddle is drawn with rounded ends.
+    rounded_paddle: bool,
+    /// The height above its top at which the paddle already catches the ball.
+    paddle_catch_tolerance: f64,
+    /// Whether the bricks are hidden until the ball comes close.
+    fog: bool,
+    /// Whether the paddle starts at a random position.
+    random_paddle_start: bool,
+    /// The seed of the game's randomness.
+    seed: Option<u64>,
+    /// How the game is colored.
+    color_mode: ColorMode,
+    /// How the bricks show the hits they take.
+    brick_style: BrickStyle,
+    /// Whether to mark where the descending ball will reach the paddle.
+    aim_assist: bool,
+    /// How long, in seconds, the ball takes to reach its full speed after launch.
+    ball_ease_in: f64,
+    /// Whether a motion blur streak is drawn behind the ball.
+    motion_blur: bool,
+    /// The number of bricks broken in a row that sets the ball on fire, `0` to never.
+    fire_streak: usize,
+    /// Whether the bricks above a cleared row drop down into it.
+    compaction: bool,
+    /// The longest time, in seconds, the ball flies without hitting anything but the walls.
+    ball_max_airtime: Option<f64>,
+    /// The region the bricks are placed in, the upper half of the playfield by default.
+    brick_region: Option<Rectf64>,
+    /// The `(width, height)` of a brick.
+    brick_size: (f64, f64),
+    /// The width of the side walls and the height of the top wall.
+    wall_thickness: (f64, f64),
+    /// The speed, in units per second, the ball gains for every brick broken.
+    speed_per_brick: f64,
+    /// The speed, in units per second, the ball gains every time it hits the paddle.
+    speed_ramp: f64,
+    /// The top speed of the ball sped up by broken bricks or the speed ramp.
+    max_ball_speed: Option<f64>,
+    /// Whether every brick is drawn in a random color.
+    random_brick_colors: bool,
+    /// Whether the ball bounces off the bottom instead of being lost.
+    bottom_bounces: bool,
+    /// The score the game starts with.
+    starting_score: usize,
+    /// The number of balls the player can lose before the game is over.
+    lives: u8,
+    /// Whether the paddle widens with every life lost on a level.
+    mercy: bool,
+    /// How the lives left are shown.
+    lives_display: LivesDisplay,
+    /// The number of levels to clear to win the game.
+    level_count: u8,
+    /// The most hits a brick takes before it's destroyed.
+    max_brick_hp: u8,
+    /// Whether the ball rests on the paddle until it's launched.
+    sticky_launch: bool,
+    /// The angle the ball leaves the paddle's edge at, if it depends on where it hits.
+    paddle_bounce_angle: Option<f64>,
+    /// The share of its speed the ball keeps moving vertically with after a bounce.
+    min_vertical_speed: f64,
+    /// The chance of a destroyed brick dropping a power-up.
+    powerup_chance: f64,
+    /// The duration, in seconds, a caught power-up resizes the paddle for.
+    powerup_duration: f64,
+    /// The most balls in play at once.
+    max_balls: usize,
+    /// The chance of a ball splitting in two when it bounces off a wall.
+    wall_split_chance: f64,
+    /// Whether the balls bounce off each other.
+    ball_collisions: bool,
+    /// Whether the game plays itself.
+    demo: bool,
+}
+
+impl Default for GameOptions {
+    fn default() -> Self {
+        Self {
+            brick_count: 0,
+            walls_color: Color::default(),
+            paddle_color: Color::default(),
+            area: Rectf64::default(),
+            ball_speed: 0.,
+            top_wall_gaps: vec![],
+            max_frame_delta: MAX_FRAME_DELTA,
+            restitution: 1.,
+            background: None,
+            paddle_deadzone: 0.,
+            brick_pulse: true,
+            bounce_jitter: 0.,
+            generator: None,
+            level_file: None,
+            level_layout: None,
+            brick_size: (BRICK_WIDTH, BRICK_HEIGHT),
+            wall_thickness: (WALL_W, WALL_H),
+            death_animation: DEATH_ANIMATION,
+            paddle_angular_factor: paddle::ANGULAR_FACTOR,
+            paddle_mass_factor: paddle::MASS_FACTOR,
+            paddle_friction: paddle::FRICTION,
+            invasion_mode: false,
+            split_depth: 0,
+            initial_direction: InitialDir::default(),
+            time_limit: None,
+            scoring: one_point,
+            paddle_tilt: false,
+            rounded_paddle: false,
+            paddle_catch_tolerance: paddle::CATCH_TOLERANCE,
+            fog: false,
+            random_paddle_start: false,
+            seed: None,
+            color_mode: ColorMode::Full,
+            brick_style: BrickStyle::default(),
+            aim_assist: false,
+            ball_ease_in: 0.,
+            motion_blur: false,
+            fire_streak: 0,
+            compaction: false,
+            ball_max_airtime: None,
+            brick_region: None,
+            speed_per_brick: 0.,
+            speed_ramp: 0.,
+            max_ball_speed: None,
+            random_brick_colors: false,
+            bottom_bounces: false,
+            starting_score: 0,
+            lives: LIVES,
+            mercy: false,
+            lives_display: LivesDisplay::Number,
+            level_count: 1,
+            max_brick_hp: 1,
+            sticky_launch: false,
+            paddle_bounce_angle: None,
+            min_vertical_speed: 0.,
+            powerup_chance: 0.,
+            powerup_duration: POWERUP_DURATION,
+            max_balls: MAX_BALLS,
+            wall_split_chance: 0.,
+            ball_collisions: false,
+            demo: false,
+        }
+    }
 }
 
 impl GameOptions {
@@ -107,253 +506,4171 @@ impl GameOptions {
         self
     }
 
+    /// Sets the initial speed of the ball.
+    ///
+    /// # Parameters
+    /// - `v`: The speed along each axis, in units per second.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
     pub fn ball_speed(mut self, v: f64) -> Self {
         self.ball_speed = v;
         self
     }
 
-    /// Builds and returns a `Game` instance with the specified options.
+    /// Speeds the ball up with every brick broken, up to [`GameOptions::max_ball_speed`].
+    ///
+    /// # Parameters
+    /// - `dv`: The speed gained per brick, in units per second. `0.0` keeps the speed.
     ///
     /// # Returns
-    /// A `Game` instance.
-    pub fn build(mut self) -> Game {
-        let brick_area = BRICK_HEIGHT * BRICK_WIDTH;
-        let available_space = (self.area.height - WALL_H) / 2.0 * (self.area.width - WALL_W * 2.0);
-        let max_brick_count = (available_space / brick_area) as u16;
-        self.brick_count = self.brick_count.min(max_brick_count);
-        let bricks_rect = Rectf64 {
-            x: self.area.x + WALL_W,
-            y: self.area.y + self.area.height / 2.0,
-            width: self.area.width - 2.0 * WALL_W,
-            height: self.area.height / 2.0 - WALL_H,
-        };
-        let pad_x = bricks_rect.width as usize % BRICK_WIDTH as usize / 2;
-        let mut coords = vec![];
-        for x in (bricks_rect.left() as usize + pad_x
-            ..=(bricks_rect.right() - BRICK_WIDTH) as usize - pad_x)
-            .step_by(BRICK_WIDTH as usize)
-        {
-            for y in (bricks_rect.bottom() as usize
-                ..bricks_rect.top() as usize - BRICK_HEIGHT as usize)
-                .step_by(BRICK_HEIGHT as usize)
-            {
-                coords.push(Rectf64 {
-                    x: x as f64,
-                    y: y as f64,
-                    width: BRICK_WIDTH,
-                    height: BRICK_HEIGHT,
-                });
-            }
-        }
-        coords.shuffle(&mut thread_rng());
-        let bricks = coords
-            .into_iter()
-            .take(self.brick_count as usize)
-            .map(|area| Brick::new(area))
-            .collect();
-        let paddle_h = self.area.height / 50.0;
-        let paddle_w = self.area.width / 10.0;
-        let paddle_area = Rectf64 {
-            x: self.area.width / 2. - paddle_w / 2. + WALL_W,
-            y: self.area.y + WALL_H,
-            width: paddle_w,
-            height: paddle_h,
-        };
-        let paddle = Paddle::new(
-            paddle_area.clone(),
-            self.area.x + WALL_W,
-            self.area.x + self.area.width - WALL_W,
-            8.0,
-            self.paddle_color,
-        );
-        let walls = Walls::new(
-            Rectf64 {
-                x: self.area.x,
-                y: self.area.y,
-                width: WALL_W,
-                height: self.area.height,
-            },
-            Rectf64 {
-                x: self.area.x + self.area.width - WALL_W,
-                y: self.area.y,
-                width: WALL_W,
-                height: self.area.height,
-            },
-            Rectf64 {
-                x: self.area.x,
-                y: self.area.y + self.area.height - WALL_H,
-                width: self.area.width,
-                height: WALL_H,
-            },
-            self.walls_color,
-        );
-        let radius = 3.;
-        let ball = Ball::new(
-            paddle_area.left() + paddle_area.width / 2. - radius,
-            paddle_area.top() + radius,
-            radius,
-            self.ball_speed,
-            self.ball_speed,
-        );
-        let bottom = Bottom::new(
-            Rectf64 {
-                x: self.area.x,
-                y: self.area.y,
-                width: self.area.width,
-                height: WALL_H,
-            },
-            Color::Gray,
-        );
+    /// The updated `GameOptions`.
+    pub fn speed_per_brick(mut self, dv: f64) -> Self {
+        self.speed_per_brick = dv;
+        self
+    }
 
-        Game {
-            area: self.area,
-            paddle,
-            ball,
-            walls,
-            bottom,
-            bricks,
-            state: Default::default(),
-            score: 0,
-        }
+    /// Speeds the ball up every time it hits the paddle, up to
+    /// [`GameOptions::max_ball_speed`], the longer the ball is kept in play the faster. A new
+    /// ball starts at the initial speed again.
+    ///
+    /// # Parameters
+    /// - `dv`: The speed gained per paddle hit, in units per second. `0.0` keeps the speed.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn speed_ramp(mut self, dv: f64) -> Self {
+        self.speed_ramp = dv.max(0.);
+        self
     }
-}
 
-/// Represents the game state and logic.
-#[derive(Debug, Default)]
-pub struct Game {
-    /// The rectangular area defining the game space.
-    area: Rectf64,
-    /// The current state of the game.
-    state: GameState,
-    /// The paddle in the game.
-    paddle: Paddle,
-    /// The ball in the game.
-    ball: Ball,
-    /// The walls in the game.
-    walls: Walls,
-    /// The bottom boundary of the game.
-    bottom: Bottom,
-    /// The bricks in the game.
-    bricks: Vec<Brick>,
-    /// The current score of the game.
-    score: usize,
-}
+    /// Sets the speed broken bricks and the speed ramp can't speed the ball up past. Defaults
+    /// to three times the initial speed.
+    ///
+    /// # Parameters
+    /// - `v`: The top speed, in units per second.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn max_ball_speed(mut self, v: f64) -> Self {
+        self.max_ball_speed = Some(v);
+        self
+    }
 
-impl Game {
-    /// Processes a game event.
+    /// Opens gaps in the top wall. A ball that escapes through a gap is lost.
     ///
     /// # Parameters
-    /// - `game_event`: The game event to process.
-    pub fn event(&mut self, game_event: GameEvent) {
-        if self.state != GameState::Running {
-            return;
-        }
+    /// - `gaps`: Horizontal ranges `(start, end)`, in game coordinates, where the top wall is open.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn top_wall_gaps(mut self, gaps: Vec<(f64, f64)>) -> Self {
+        self.top_wall_gaps = gaps;
+        self
+    }
 
-        match game_event {
-            GameEvent::MovePad { direction } => match direction {
-                Direction::Left => {
-                    self.paddle.mov(Direction::Left);
-                }
-                Direction::Right => {
-                    self.paddle.mov(Direction::Right);
-                }
-                #[cfg(feature = "debug")]
-                _ => unreachable!(),
-            },
-            #[cfg(feature = "debug")]
-            GameEvent::MoveBallManual { direction } => {
-                self.ball.mov_dir(direction);
-                self.check_collisions();
-            }
-            GameEvent::Tick => {
-                self.ball.mov();
-                self.check_collisions();
-            }
-        }
+    /// Sets the longest time step the simulation takes in one tick. Larger deltas, e.g. after
+    /// the terminal was suspended, are clamped so the ball doesn't jump across the field.
+    ///
+    /// # Parameters
+    /// - `dt`: The maximum time delta, in seconds.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn max_frame_delta(mut self, dt: f64) -> Self {
+        self.max_frame_delta = dt;
+        self
     }
 
-    /// Moves the ball and checks for collisions.
+    /// Sets the coefficient of restitution of the ball's bounces off the walls and the bricks.
+    /// A ball slowed down by them is brought back to its launch speed by the paddle, and one
+    /// sped up doesn't get faster than [`GameOptions::max_ball_speed`].
     ///
     /// # Parameters
-    /// - `dt`: The time delta for the movement.
+    /// - `restitution`: The share of velocity kept after a bounce, `1.0` for perfectly
+    ///   elastic bounces.
     ///
-    /// TODO: maybe I need to predict collisions
-    /// instead of acting upon them, but for now
-    /// this implementation is ok.
-    pub fn check_collisions(&mut self) {
-        // Process ball collision with the walls and the paddle.
-        self.ball.collision(&mut self.walls.left);
-        self.ball.collision(&mut self.walls.right);
-        self.ball.collision(&mut self.walls.top);
-        self.ball.collision(&mut self.paddle);
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn restitution(mut self, restitution: f64) -> Self {
+        self.restitution = restitution;
+        self
+    }
 
-        // Move the ball and check if it possibly
-        // fell down. If yes - the game is lost.
-        if self.ball.collision(&mut self.bottom) {
-            self.state = GameState::Lost;
-            return;
-        }
+    /// Sets how long the ball takes to accelerate from standstill to its full speed after
+    /// launch.
+    ///
+    /// # Parameters
+    /// - `duration`: The duration of the acceleration, in seconds. `0.0` disables it.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn ball_ease_in(mut self, duration: f64) -> Self {
+        self.ball_ease_in = duration;
+        self
+    }
 
-        // Check if the ball collided with any of the "closest" bricks and if it did - remove those.
-        self.bricks
-            .sort_by(|b1, b2| self.ball.dsquared(b1).total_cmp(&self.ball.dsquared(b2)));
-        let (closest, mut other): (Vec<_>, Vec<_>) = std::mem::take(&mut self.bricks)
-            .into_iter()
-            .partition(|brick| self.ball.collision(brick));
-        self.score += closest.len();
+    /// Draws a motion blur streak behind the ball.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to draw the streak.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn motion_blur(mut self, enabled: bool) -> Self {
+        self.motion_blur = enabled;
+        self
+    }
 
-        // If no bricks left - the game is won.
-        if other.is_empty() {
-            self.state = GameState::Won;
-        }
-        std::mem::swap(&mut self.bricks, &mut other);
+    /// Sets a text drawn faintly behind everything else, e.g. the name of the level.
+    ///
+    /// # Parameters
+    /// - `text`: The background text.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn background(mut self, text: String) -> Self {
+        self.background = Some(text);
+        self
     }
-}
 
-impl Shape for Game {
-    fn draw(&self, painter: &mut Painter) {
-        self.walls.draw(painter);
-        self.paddle.draw(painter);
-        self.ball.draw(painter);
-        self.bricks.iter().for_each(|brick| brick.draw(painter));
+    /// Sets the central dead zone of the paddle, where the ball bounces back vertically
+    /// regardless of the paddle's movement.
+    ///
+    /// # Parameters
+    /// - `deadzone`: The half-width of the dead zone as a share of the paddle's half-width.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn paddle_deadzone(mut self, deadzone: f64) -> Self {
+        self.paddle_deadzone = deadzone;
+        self
+    }
 
-        match &self.state {
-            GameState::Lost => {
-                Word::new(
-                    "game over".to_string(),
-                    (
-                        self.area.x + self.area.width * 0.35,
-                        self.area.y + self.area.height / 2.,
-                    ),
-                    12.0,
-                    Color::Red,
-                )
-                .draw(painter);
-            }
-            GameState::Won => {
-                Word::new(
-                    "you won".to_string(),
-                    (
-                        self.area.x + self.area.width * 0.35,
-                        self.area.y + self.area.height / 2.,
-                    ),
+    /// Sets whether the bricks gently pulse while the game is idle, e.g. paused.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to animate the bricks.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn brick_pulse(mut self, enabled: bool) -> Self {
+        self.brick_pulse = enabled;
+        self
+    }
+
+    /// Sets the random deflection applied when the ball bounces off a brick. A little
+    /// randomness breaks repetitive loops between the same bricks.
+    ///
+    /// # Parameters
+    /// - `jitter`: The largest deflection of the bounce angle, in radians.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn bounce_jitter(mut self, jitter: f64) -> Self {
+        self.bounce_jitter = jitter.abs();
+        self
+    }
+
+    /// Uses a generated, symmetric brick layout instead of randomly scattered bricks.
+    /// The brick count is ignored.
+    ///
+    /// # Parameters
+    /// - `seed`: The seed of the layout.
+    /// - `difficulty`: The difficulty of the level, `0` being the easiest.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn generator(mut self, seed: u64, difficulty: u8) -> Self {
+        self.generator = Some((seed, difficulty));
+        self
+    }
+
+    /// Reads a fixed brick layout from a level file, see [`levels::parse`] for its format,
+    /// instead of placing the bricks randomly. The brick count and the most hits a brick takes
+    /// are ignored. A missing or malformed file falls back to the random layout.
+    ///
+    /// # Parameters
+    /// - `path`: The level file.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn level_file(mut self, path: PathBuf) -> Self {
+        self.level_file = Some(path);
+        self
+    }
+
+    /// Uses a fixed brick layout, see [`levels::parse`] for its format, read beforehand, e.g.
+    /// from the standard input. Takes precedence over [`GameOptions::level_file`], and falls
+    /// back to the random layout the same way.
+    ///
+    /// # Parameters
+    /// - `layout`: The text of the level.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn level_layout(mut self, layout: String) -> Self {
+        self.level_layout = Some(layout);
+        self
+    }
+
+    /// Sets how long the lost ball flashes before the game is over.
+    ///
+    /// # Parameters
+    /// - `duration`: The duration of the animation in seconds, `0.0` to skip it.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn death_animation(mut self, duration: f64) -> Self {
+        self.death_animation = duration.max(0.);
+        self
+    }
+
+    /// Sets how strongly the angle of impact turns the paddle's movement into spin.
+    ///
+    /// # Parameters
+    /// - `factor`: The angular factor of the paddle's spin.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn paddle_angular_factor(mut self, factor: f64) -> Self {
+        self.paddle_angular_factor = factor;
+        self
+    }
+
+    /// Sets the mass of the paddle relative to the ball. Heavier paddles give more spin.
+    ///
+    /// # Parameters
+    /// - `factor`: The mass factor of the paddle's spin.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn paddle_mass_factor(mut self, factor: f64) -> Self {
+        self.paddle_mass_factor = factor;
+        self
+    }
+
+    /// Sets the friction between the ball and the paddle. With more friction the paddle's
+    /// movement has more influence on the ball.
+    ///
+    /// # Parameters
+    /// - `friction`: The friction coefficient.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn paddle_friction(mut self, friction: f64) -> Self {
+        self.paddle_friction = friction;
+        self
+    }
+
+    /// Enables the invasion mode: every few seconds the bricks move one row down and a new
+    /// row spawns at the top. The game is lost once the bricks reach the paddle.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to enable the invasion mode.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn invasion_mode(mut self, enabled: bool) -> Self {
+        self.invasion_mode = enabled;
+        self
+    }
+
+    /// Makes the bricks above a cleared row drop down one row, like in Tetris.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to enable the compaction.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn compaction(mut self, enabled: bool) -> Self {
+        self.compaction = enabled;
+        self
+    }
+
+    /// Nudges the ball down towards the paddle once it flies too long without hitting the
+    /// paddle or a brick, so it can't bounce between the walls forever.
+    ///
+    /// # Parameters
+    /// - `airtime`: The longest flight in seconds, or `None` to let the ball fly forever.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn ball_max_airtime(mut self, airtime: Option<f64>) -> Self {
+        self.ball_max_airtime = airtime;
+        self
+    }
+
+    /// Marks where the descending ball will reach the paddle, and the path the ball resting
+    /// on the paddle will be launched along.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to show the markers.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn aim_assist(mut self, enabled: bool) -> Self {
+        self.aim_assist = enabled;
+        self
+    }
+
+    /// Sets the ball on fire once it breaks enough bricks without touching the paddle.
+    ///
+    /// # Parameters
+    /// - `bricks`: The number of bricks to break in a row, `0` to disable the effect.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn fire_streak(mut self, bricks: usize) -> Self {
+        self.fire_streak = bricks;
+        self
+    }
+
+    /// Sets how the game is colored.
+    ///
+    /// # Parameters
+    /// - `mode`: The color mode, e.g. [`ColorMode::Monochrome`] for terminals without colors.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn color_mode(mut self, mode: ColorMode) -> Self {
+        self.color_mode = mode;
+        self
+    }
+
+    /// Sets the colors of the multi-hit bricks, by the number of hits they have left. A brick
+    /// with more hits left than there are colors takes the last one.
+    ///
+    /// # Parameters
+    /// - `colors`: The colors for one hit left, two hits left and so on, e.g. yellow, orange
+    ///   and red. Empty to draw the multi-hit bricks in their regular color.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn durability_ramp(mut self, colors: Vec<Color>) -> Self {
+        self.brick_style.durability_ramp = colors;
+        self
+    }
+
+    /// Draws the number of hits a multi-hit brick has left in its middle, on the bricks large
+    /// enough for it.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to show the numbers.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn brick_hp_digits(mut self, enabled: bool) -> Self {
+        self.brick_style.hp_digits = enabled;
+        self
+    }
+
+    /// Draws a bar along the top of a multi-hit brick, filled by the share of the hits it has
+    /// left, on the bricks large enough for it.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to show the bars.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn brick_health_bars(mut self, enabled: bool) -> Self {
+        self.brick_style.health_bars = enabled;
+        self
+    }
+
+    /// Seeds the randomness of the game, e.g. the brick layout, the bounce jitter or the
+    /// random launch direction, so that the same seed plays out the same way.
+    ///
+    /// # Parameters
+    /// - `seed`: The seed of the game's random number generator.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Places the paddle at a random position when the game starts. The ball is launched from
+    /// the paddle wherever it is.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to randomize the start position.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn random_paddle_start(mut self, enabled: bool) -> Self {
+        self.random_paddle_start = enabled;
+        self
+    }
+
+    /// Hides the bricks in a fog. Each brick is revealed once the ball comes close to it.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to hide the bricks.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn fog(mut self, enabled: bool) -> Self {
+        self.fog = enabled;
+        self
+    }
+
+    /// Makes the paddle briefly highlight where the ball hit it.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to show the highlight.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn paddle_tilt(mut self, enabled: bool) -> Self {
+        self.paddle_tilt = enabled;
+        self
+    }
+
+    /// Sets how far above its top the paddle already catches the ball.
+    ///
+    /// # Parameters
+    /// - `tolerance`: The height above the paddle, `0.0` to catch only the ball touching it.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn paddle_catch_tolerance(mut self, tolerance: f64) -> Self {
+        self.paddle_catch_tolerance = tolerance;
+        self
+    }
+
+    /// Makes the ball bounce off the paddle at an angle depending on where it hits, instead
+    /// of spinning it with the paddle's movement. See [`Paddle::angled`].
+    ///
+    /// # Parameters
+    /// - `max_angle`: The angle, in radians from straight up, the ball leaves the edges at.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn paddle_bounce_angle(mut self, max_angle: f64) -> Self {
+        self.paddle_bounce_angle = Some(max_angle);
+        self
+    }
+
+    /// Draws the paddle with rounded ends.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to round the ends.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn rounded_paddle(mut self, enabled: bool) -> Self {
+        self.rounded_paddle = enabled;
+        self
+    }
+
+    /// Sets how the points awarded for breaking a brick are computed. By default every brick
+    /// is worth a single point.
+    ///
+    /// # Parameters
+    /// - `scoring`: The function computing the points of a broken brick.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn scoring(mut self, scoring: Scoring) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    /// Limits how long the game can be played for. Once the limit runs out the game ends in
+    /// the [`GameState::TimeUp`] state.
+    ///
+    /// # Parameters
+    /// - `limit`: The limit of the play time, in seconds. Pauses don't count.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn time_limit(mut self, limit: f64) -> Self {
+        self.time_limit = Some(limit);
+        self
+    }
+
+    /// Sets the direction the ball is launched in.
+    ///
+    /// # Parameters
+    /// - `direction`: The launch direction.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn initial_direction(mut self, direction: InitialDir) -> Self {
+        self.initial_direction = direction;
+        self
+    }
+
+    /// Sets the size of the bricks, e.g. smaller ones to fit more of them. The bricks are
+    /// laid out on whole units, so the size is rounded and kept at least one unit.
+    ///
+    /// # Parameters
+    /// - `width`: The width of a brick.
+    /// - `height`: The height of a brick.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn brick_size(mut self, width: f64, height: f64) -> Self {
+        self.brick_size = (width.round().max(1.), height.round().max(1.));
+        self
+    }
+
+    /// Sets the thickness of the walls, at least one unit.
+    ///
+    /// # Parameters
+    /// - `width`: The width of the side walls.
+    /// - `height`: The height of the top wall.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn wall_thickness(mut self, width: f64, height: f64) -> Self {
+        self.wall_thickness = (width.max(1.), height.max(1.));
+        self
+    }
+
+    /// Sets the region the bricks are placed in instead of the upper half of the playfield,
+    /// e.g. to bring them closer to the paddle. The region is clipped to the space between
+    /// the walls and kept clear of the paddle and the ball resting on it.
+    ///
+    /// # Parameters
+    /// - `region`: The region for the bricks.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn brick_region(mut self, region: Rectf64) -> Self {
+        self.brick_region = Some(region);
+        self
+    }
+
+    /// Sets the number of balls the player can lose before the game is over. A lost ball
+    /// is launched again from the paddle while there are lives left.
+    ///
+    /// # Parameters
+    /// - `lives`: The number of lives, at least one.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn lives(mut self, lives: u8) -> Self {
+        self.lives = lives.max(1);
+        self
+    }
+
+    /// Widens the paddle a little for every life lost on a level, up to a limit, to help a
+    /// struggling player. The paddle gets its original width back once the level is cleared.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to widen the paddle.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn mercy(mut self, enabled: bool) -> Self {
+        self.mercy = enabled;
+        self
+    }
+
+    /// Sets how the lives left are shown.
+    ///
+    /// # Parameters
+    /// - `display`: The number of lives, or a ball icon for each of them.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn lives_display(mut self, display: LivesDisplay) -> Self {
+        self.lives_display = display;
+        self
+    }
+
+    /// Sets the number of levels to clear to win the game. Every level after the first one
+    /// is laid out with more bricks and a faster ball, keeping the score and the lives.
+    ///
+    /// # Parameters
+    /// - `count`: The number of levels, at least one.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn level_count(mut self, count: u8) -> Self {
+        self.level_count = count.max(1);
+        self
+    }
+
+    /// Makes the bricks take several hits before they're destroyed, each a random number
+    /// of them up to `hp`.
+    ///
+    /// # Parameters
+    /// - `hp`: The most hits a brick takes, at least one.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn max_brick_hp(mut self, hp: u8) -> Self {
+        self.max_brick_hp = hp.max(1);
+        self
+    }
+
+    /// Makes the ball rest on the paddle, moving along with it, at the start of the game and
+    /// of every life until [`GameEvent::Launch`]. The ball is launched in the direction the
+    /// paddle last moved in, if it moved.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to hold the ball until it's launched.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn sticky_launch(mut self, enabled: bool) -> Self {
+        self.sticky_launch = enabled;
+        self
+    }
+
+    /// Keeps the ball from bouncing between the side walls forever, see
+    /// [`Ball::min_vertical_speed`].
+    ///
+    /// # Parameters
+    /// - `share`: The least share of its speed the ball moves vertically with after a
+    ///   bounce, between `0.0` and `1.0`.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn min_vertical_speed(mut self, share: f64) -> Self {
+        self.min_vertical_speed = share;
+        self
+    }
+
+    /// Makes destroyed bricks drop power-ups, see [`PowerUpKind`], now and then. The
+    /// power-ups fall down and take effect once caught with the paddle.
+    ///
+    /// # Parameters
+    /// - `chance`: The chance of a brick dropping a power-up, between `0.0` and `1.0`.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn powerup_chance(mut self, chance: f64) -> Self {
+        self.powerup_chance = chance.clamp(0., 1.);
+        self
+    }
+
+    /// Sets how long a caught power-up resizes the paddle for.
+    ///
+    /// # Parameters
+    /// - `duration`: The duration, in seconds.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn powerup_duration(mut self, duration: f64) -> Self {
+        self.powerup_duration = duration;
+        self
+    }
+
+    /// Sets the most balls in play at once. Once it's reached, whatever splits the balls
+    /// doesn't add any more of them.
+    ///
+    /// # Parameters
+    /// - `count`: The number of balls, at least one.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn max_balls(mut self, count: usize) -> Self {
+        self.max_balls = count.max(1);
+        self
+    }
+
+    /// Sets the chance of a ball splitting in two when it bounces off a wall, both halves
+    /// keeping its speed but turned apart a little. The balls stop splitting once there are
+    /// as many of them as allowed by [`GameOptions::max_balls`].
+    ///
+    /// # Parameters
+    /// - `chance`: The chance, from `0.0` for never to `1.0` for every bounce.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn wall_split_chance(mut self, chance: f64) -> Self {
+        self.wall_split_chance = chance.clamp(0., 1.);
+        self
+    }
+
+    /// Makes the balls bounce off each other instead of passing through.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to collide the balls.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn ball_collisions(mut self, enabled: bool) -> Self {
+        self.ball_collisions = enabled;
+        self
+    }
+
+    /// Lets the game play itself, launching the ball and moving the paddle on every
+    /// [`GameEvent::Tick`] the way [`Game::ai_suggest_move`] suggests.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to play the demo.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn demo(mut self, enabled: bool) -> Self {
+        self.demo = enabled;
+        self
+    }
+
+    /// Sets the score the game starts with, e.g. for a challenge to keep it from dropping.
+    ///
+    /// # Parameters
+    /// - `score`: The starting score.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn starting_score(mut self, score: usize) -> Self {
+        self.starting_score = score;
+        self
+    }
+
+    /// Makes the ball bounce off the bottom instead of being lost, for a casual game the
+    /// paddle missing the ball doesn't end.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to bounce the ball back.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn bottom_bounces(mut self, enabled: bool) -> Self {
+        self.bottom_bounces = enabled;
+        self
+    }
+
+    /// Draws every brick in a random color from a palette. Purely cosmetic, the colors are
+    /// picked with the game's seed.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to randomize the colors.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn random_brick_colors(mut self, enabled: bool) -> Self {
+        self.random_brick_colors = enabled;
+        self
+    }
+
+    /// Makes the bricks split into two smaller halves when destroyed.
+    ///
+    /// # Parameters
+    /// - `depth`: How many times a brick and its halves split before disappearing, capped at
+    ///   [`crate::brick::MAX_SPLIT_DEPTH`]. `0` disables splitting.
+    ///
+    /// # Returns
+    /// The updated `GameOptions`.
+    pub fn split_depth(mut self, depth: u8) -> Self {
+        self.split_depth = depth;
+        self
+    }
+
+    /// Checks that the level given with [`GameOptions::level_layout`] or
+    /// [`GameOptions::level_file`] loads, as [`GameOptions::build`] falls back to the random
+    /// layout without telling, e.g. to report the error to the player before the game starts.
+    ///
+    /// # Returns
+    /// An error if the level can't be read or is malformed, `Ok` if it loads or there's none.
+    pub fn check_level(&self) -> anyhow::Result<()> {
+        match self.load_level(&self.bricks_region()) {
+            Some(loaded) => loaded.map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the region the bricks are laid out in, the upper half of the playfield unless
+    /// it's set with [`GameOptions::brick_region`].
+    fn bricks_region(&self) -> Rectf64 {
+        let (wall_w, wall_h) = self.wall_thickness;
+        let paddle_h = self.area.height / 50.0;
+        match &self.brick_region {
+            Some(region) => {
+                // Leave room for the ball resting on the paddle.
+                let left = region.left().max(self.area.x + wall_w);
+                let right = region.right().min(self.area.right() - wall_w);
+                let bottom = region
+                    .bottom()
+                    .max(self.area.y + wall_h + paddle_h + 2. * BALL_RADIUS);
+                let top = region.top().min(self.area.top() - wall_h);
+                // The grid is laid out on whole units.
+                let (left, bottom) = (left.ceil(), bottom.ceil());
+                Rectf64 {
+                    x: left,
+                    y: bottom,
+                    width: (right.floor() - left).max(0.),
+                    height: (top.floor() - bottom).max(0.),
+                }
+            }
+            None => Rectf64 {
+                x: self.area.x + wall_w,
+                y: self.area.y + self.area.height / 2.0,
+                width: self.area.width - 2.0 * wall_w,
+                height: self.area.height / 2.0 - wall_h,
+            },
+        }
+    }
+
+    /// Loads the fixed brick layout, if there's one.
+    ///
+    /// # Parameters
+    /// - `region`: The region the bricks are laid out in.
+    ///
+    /// # Returns
+    /// The level or the error loading it, `None` without a level.
+    fn load_level(&self, region: &Rectf64) -> Option<anyhow::Result<Level>> {
+        match (&self.level_layout, &self.level_file) {
+            (Some(layout), _) => Some(levels::load(layout.as_bytes(), region, self.brick_size)),
+            (None, Some(path)) => Some(
+                std::fs::File::open(path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|file| levels::load(file, region, self.brick_size))
+                    .with_context(|| format!("failed to load the level {}", path.display())),
+            ),
+            (None, None) => None,
+        }
+    }
+
+    /// Builds and returns a `Game` instance with the specified options.
+    ///
+    /// # Returns
+    /// A `Game` instance.
+    pub fn build(mut self) -> Game {
+        let options = self.clone();
+        let (brick_w, brick_h) = self.brick_size;
+        let (wall_w, wall_h) = self.wall_thickness;
+        let paddle_h = self.area.height / 50.0;
+        let paddle_w = self.area.width / 10.0;
+        let bricks_rect = self.bricks_region();
+        let brick_area = brick_h * brick_w;
+        let max_brick_count = (bricks_rect.width * bricks_rect.height / brick_area) as u16;
+        self.brick_count = self.brick_count.min(max_brick_count);
+        // The top row of the brick grid, where new bricks spawn in invasion mode.
+        let pad_x = bricks_rect.width % brick_w / 2.;
+        let spawn_row = (0..)
+            .map(|col| bricks_rect.left() + pad_x + col as f64 * brick_w)
+            .take_while(|x| x + brick_w <= bricks_rect.right() - pad_x)
+            .map(|x| Rectf64 {
+                x,
+                y: bricks_rect.top() - brick_h,
+                width: brick_w,
+                height: brick_h,
+            })
+            .collect();
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let loaded = self.load_level(&bricks_rect).and_then(|loaded| {
+            loaded
+                .map_err(|e| tracing::warn!("{e:#}, falling back to the random layout"))
+                .ok()
+        });
+        let from_file = loaded.is_some();
+        // The gaps of the level file add to the ones opened with the options.
+        let mut top_gaps = self.top_wall_gaps.clone();
+        let bricks = match (loaded, self.generator) {
+            (Some(level), _) => {
+                top_gaps.extend(level.top_gaps);
+                level.bricks
+            }
+            (None, Some((seed, difficulty))) => {
+                levels::generate(seed, difficulty, &bricks_rect, (brick_w, brick_h))
+            }
+            (None, None) => {
+                let pad_x = bricks_rect.width as usize % brick_w as usize / 2;
+                let mut coords = vec![];
+                for x in (bricks_rect.left() as usize + pad_x
+                    ..=((bricks_rect.right() - brick_w) as usize).saturating_sub(pad_x))
+                    .step_by(brick_w as usize)
+                {
+                    for y in (bricks_rect.bottom() as usize
+                        ..(bricks_rect.top() as usize).saturating_sub(brick_h as usize))
+                        .step_by(brick_h as usize)
+                    {
+                        coords.push(Rectf64 {
+                            x: x as f64,
+                            y: y as f64,
+                            width: brick_w,
+                            height: brick_h,
+                        });
+                    }
+                }
+                coords.shuffle(&mut rng);
+                coords
+                    .into_iter()
+                    .take(self.brick_count as usize)
+                    .map(Brick::new)
+                    .collect()
+            }
+        };
+        let mut bricks: Vec<_> = bricks
+            .into_iter()
+            .map(|brick| brick.splitting(self.split_depth).hidden(self.fog))
+            .collect();
+        let (min_x, max_x) = (self.area.x + wall_w, self.area.x + self.area.width - wall_w);
+        let paddle_area = Rectf64 {
+            x: if self.random_paddle_start {
+                // Walls too thick to fit the paddle between leave it against the left one.
+                rng.gen_range(min_x..=(max_x - paddle_w).max(min_x))
+            } else {
+                self.area.width / 2. - paddle_w / 2. + wall_w
+            },
+            y: self.area.y + wall_h,
+            width: paddle_w,
+            height: paddle_h,
+        };
+        let mode = self.color_mode;
+        let paddle = Paddle::new(
+            paddle_area.clone(),
+            min_x,
+            max_x,
+            8.0,
+            mode.apply(self.paddle_color),
+        )
+        .deadzone(self.paddle_deadzone)
+        .spin(
+            self.paddle_angular_factor,
+            self.paddle_mass_factor,
+            self.paddle_friction,
+        )
+        // The highlight can't be told apart without colors.
+        .tilt(self.paddle_tilt && mode == ColorMode::Full)
+        .rounded(self.rounded_paddle)
+        .catch_tolerance(self.paddle_catch_tolerance)
+        .angled(self.paddle_bounce_angle);
+        let walls = Walls::new(
+            Rectf64 {
+                x: self.area.x,
+                y: self.area.y,
+                width: wall_w,
+                height: self.area.height,
+            },
+            Rectf64 {
+                x: self.area.x + self.area.width - wall_w,
+                y: self.area.y,
+                width: wall_w,
+                height: self.area.height,
+            },
+            Rectf64 {
+                x: self.area.x,
+                y: self.area.y + self.area.height - wall_h,
+                width: self.area.width,
+                height: wall_h,
+            },
+            mode.apply(self.walls_color),
+        )
+        .top_gaps(top_gaps);
+        let left = match self.initial_direction {
+            InitialDir::UpLeft => true,
+            InitialDir::UpRight => false,
+            InitialDir::Random => rng.gen_bool(0.5),
+        };
+        if self.random_brick_colors {
+            bricks = bricks
+                .into_iter()
+                .map(|brick| brick.colored(*BRICK_PALETTE.choose(&mut rng).unwrap()))
+                .collect();
+        }
+        if self.max_brick_hp > 1 && !from_file {
+            bricks = bricks
+                .into_iter()
+                .map(|brick| brick.with_hp(rng.gen_range(1..=self.max_brick_hp)))
+                .collect();
+        }
+        let radius = BALL_RADIUS;
+        // The ball starts clear of the catch tolerance, so the paddle doesn't bounce it back.
+        let ball = Ball::new(
+            paddle_area.left() + paddle_area.width / 2. - radius,
+            paddle.collision_area().top() + radius,
+            radius,
+            if left {
+                -self.ball_speed
+            } else {
+                self.ball_speed
+            },
+            self.ball_speed,
+        )
+        .restitution(self.restitution)
+        .ease_in(self.ball_ease_in)
+        .motion_blur(self.motion_blur)
+        .min_vertical_speed(self.min_vertical_speed);
+        let bottom = Bottom::new(
+            Rectf64 {
+                x: self.area.x,
+                y: self.area.y,
+                width: self.area.width,
+                height: wall_h,
+            },
+            mode.apply(Color::Gray),
+        )
+        .bouncing(self.bottom_bounces);
+
+        Game {
+            area: self.area,
+            paddle,
+            spawn: ball.clone(),
+            balls: vec![ball],
+            lives: self.lives,
+            mercy: self.mercy,
+            deaths: 0,
+            lives_display: self.lives_display,
+            level: 1,
+            level_count: self.level_count,
+            level_options: options,
+            banner: if self.level_count > 1 {
+                LEVEL_BANNER
+            } else {
+                0.
+            },
+            walls,
+            bottom,
+            bricks,
+            brick_height: brick_h,
+            sticky_launch: self.sticky_launch,
+            ball_attached: self.sticky_launch,
+            powerup_chance: self.powerup_chance,
+            powerups: vec![],
+            powerup_duration: self.powerup_duration,
+            max_balls: self.max_balls,
+            wall_split_chance: self.wall_split_chance,
+            ball_collisions: self.ball_collisions,
+            demo: self.demo,
+            demo_wait: 0.,
+            resized: None,
+            launch_sign: None,
+            state: Default::default(),
+            score: self.starting_score,
+            carried_score: None,
+            max_frame_delta: self.max_frame_delta,
+            popups: vec![],
+            background: self.background,
+            brick_pulse: self.brick_pulse,
+            pulse_phase: 0.,
+            bounce_jitter: self.bounce_jitter,
+            rng,
+            death_animation: self.death_animation,
+            dying: None,
+            invasion: self.invasion_mode.then_some(Invasion {
+                spawn_row,
+                timer: 0.,
+            }),
+            split_depth: self.split_depth,
+            time_limit: self.time_limit,
+            play_time: 0.,
+            tick_count: 0,
+            scoring: self.scoring,
+            combo: 0,
+            fog: self.fog,
+            color_mode: mode,
+            brick_style: self.brick_style.clone(),
+            aim_assist: self.aim_assist,
+            fire_streak: self.fire_streak,
+            compaction: self.compaction,
+            ball_max_airtime: self.ball_max_airtime,
+            speed_per_brick: self.speed_per_brick,
+            speed_ramp: self.speed_ramp,
+            restitution: self.restitution,
+            paddle_hits: 0,
+            max_ball_speed: self
+                .max_ball_speed
+                .unwrap_or(MAX_SPEED_FACTOR * self.ball_speed.hypot(self.ball_speed)),
+            #[cfg(feature = "debug")]
+            effects: EffectLog::default(),
+        }
+    }
+}
+
+/// A short-lived label shown where points were scored.
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct ScorePopup {
+    /// The text of the popup.
+    text: String,
+    /// Where the popup is drawn.
+    position: (f64, f64),
+    /// The remaining time, in seconds, the popup stays on screen.
+    ttl: f64,
+}
+
+/// The state of the invasion mode.
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Invasion {
+    /// Where the bricks of a new row spawn.
+    spawn_row: Vec<Rectf64>,
+    /// The time, in seconds, since the last row descended.
+    timer: f64,
+}
+
+/// A fixed-size log of the most recent effects, dropping the oldest ones.
+#[cfg(feature = "debug")]
+#[derive(Debug, Default)]
+struct EffectLog {
+    effects: std::collections::VecDeque<GameEffect>,
+}
+
+#[cfg(feature = "debug")]
+impl EffectLog {
+    /// Records an effect, forgetting the oldest one if the log is full.
+    fn push(&mut self, effect: GameEffect) {
+        if self.effects.len() == EFFECT_LOG_SIZE {
+            self.effects.pop_front();
+        }
+        self.effects.push_back(effect);
+    }
+}
+
+/// Represents the game state and logic.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Game {
+    /// The rectangular area defining the game space.
+    area: Rectf64,
+    /// The current state of the game.
+    state: GameState,
+    /// The paddle in the game.
+    paddle: Paddle,
+    /// The balls in play, at least one.
+    balls: Vec<Ball>,
+    /// The ball as launched at the start, relaunched after losing a life.
+    spawn: Ball,
+    /// The number of balls the player can still lose, the one in play included.
+    lives: u8,
+    /// Whether the paddle widens with every life lost on a level.
+    mercy: bool,
+    /// The number of lives lost on the current level.
+    deaths: u8,
+    /// How the lives left are shown.
+    lives_display: LivesDisplay,
+    /// The level being played, counted from one.
+    level: u8,
+    /// The number of levels to clear to win the game.
+    level_count: u8,
+    /// The options the game was built with, the next levels are laid out from.
+    level_options: GameOptions,
+    /// The remaining time, in seconds, the banner announcing the level is shown for.
+    banner: f64,
+    /// Whether the ball rests on the paddle until it's launched.
+    sticky_launch: bool,
+    /// The chance of a destroyed brick dropping a power-up.
+    powerup_chance: f64,
+    /// The power-ups falling towards the paddle.
+    powerups: Vec<PowerUp>,
+    /// The duration, in seconds, a caught power-up resizes the paddle for.
+    powerup_duration: f64,
+    /// The most balls in play at once.
+    max_balls: usize,
+    /// The chance of a ball splitting in two when it bounces off a wall.
+    wall_split_chance: f64,
+    /// Whether the balls bounce off each other.
+    ball_collisions: bool,
+    /// Whether the game plays itself.
+    demo: bool,
+    /// The remaining time, in seconds, until the demo moves the paddle again.
+    demo_wait: f64,
+    /// The remaining time, in seconds, the paddle stays resized for, if it is.
+    resized: Option<f64>,
+    /// Whether the ball is resting on the paddle.
+    ball_attached: bool,
+    /// The horizontal direction, `-1.0` or `1.0`, the paddle moved in while holding the ball.
+    launch_sign: Option<f64>,
+    /// The walls in the game.
+    walls: Walls,
+    /// The bottom boundary of the game.
+    bottom: Bottom,
+    /// The bricks in the game.
+    bricks: Vec<Brick>,
+    /// The height of a brick, a row of bricks drops by.
+    brick_height: f64,
+    /// The current score of the game.
+    score: usize,
+    /// The score of the earlier games of the session, if it's carried over.
+    carried_score: Option<usize>,
+    /// The longest time step, in seconds, the simulation takes at once.
+    max_frame_delta: f64,
+    /// Popups for recently scored bonuses.
+    popups: Vec<ScorePopup>,
+    /// Text drawn faintly behind everything else.
+    background: Option<String>,
+    /// Whether the bricks pulse while the game is idle.
+    brick_pulse: bool,
+    /// The time, in seconds, the game has been idle for.
+    pulse_phase: f64,
+    /// The largest random deflection, in radians, applied when the ball bounces off a brick.
+    bounce_jitter: f64,
+    /// The random number generator driving the game's randomness, reseeded when the game is
+    /// resumed.
+    #[cfg_attr(feature = "serde", serde(skip, default = "StdRng::from_entropy"))]
+    rng: StdRng,
+    /// The duration, in seconds, of the animation played when the ball is lost.
+    death_animation: f64,
+    /// The remaining time, in seconds, of the ball loss animation, if it's playing.
+    dying: Option<f64>,
+    /// The invasion mode state, if enabled.
+    invasion: Option<Invasion>,
+    /// How many times new bricks split in two before disappearing.
+    split_depth: u8,
+    /// The longest time, in seconds, the game can be played for.
+    time_limit: Option<f64>,
+    /// The time, in seconds, the game has been played for.
+    play_time: f64,
+    /// The number of ticks the game has been simulated for.
+    tick_count: u64,
+    /// Computes the points awarded for breaking a brick. A function can't be saved, so a
+    /// resumed game falls back to the default scoring.
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_scoring"))]
+    scoring: Scoring,
+    /// The number of bricks broken since the ball last bounced off the paddle.
+    combo: usize,
+    /// Whether new bricks are hidden until the ball comes close.
+    fog: bool,
+    /// How the game is colored.
+    color_mode: ColorMode,
+    /// How the bricks show the hits they take.
+    brick_style: BrickStyle,
+    /// Whether to mark where the descending ball will reach the paddle.
+    aim_assist: bool,
+    /// The number of bricks broken in a row that sets the ball on fire, `0` to never.
+    fire_streak: usize,
+    /// Whether the bricks above a cleared row drop down into it.
+    compaction: bool,
+    /// The longest time, in seconds, the ball flies without hitting anything but the walls.
+    ball_max_airtime: Option<f64>,
+    /// The speed, in units per second, the ball gains for every brick broken.
+    speed_per_brick: f64,
+    /// The speed, in units per second, the ball gains every time it hits the paddle.
+    speed_ramp: f64,
+    /// The share of the ball's velocity kept after bouncing off a wall or a brick.
+    restitution: f64,
+    /// The number of times the ball hit the paddle.
+    paddle_hits: usize,
+    /// The top speed of the ball sped up by broken bricks or the speed ramp.
+    max_ball_speed: f64,
+    /// The most recent effects of the simulation.
+    #[cfg(feature = "debug")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    effects: EffectLog,
+}
+
+impl Game {
+    /// Forces the game into the given state, skipping the gameplay needed to reach it.
+    ///
+    /// # Parameters
+    /// - `state`: The state to switch to.
+    #[cfg(test)]
+    pub fn force_state(&mut self, state: GameState) {
+        self.state = state;
+    }
+
+    /// Returns the current state of the game.
+    pub fn state(&self) -> GameState {
+        self.state.clone()
+    }
+
+    /// Returns the area of the playfield.
+    pub fn area(&self) -> Rectf64 {
+        self.area.clone()
+    }
+
+    /// Returns the bricks left in the game.
+    pub fn bricks(&self) -> &[Brick] {
+        &self.bricks
+    }
+
+    /// Returns the balls in play.
+    pub fn balls(&self) -> &[Ball] {
+        &self.balls
+    }
+
+    /// Returns the position of the first ball's center.
+    pub fn ball_position(&self) -> (f64, f64) {
+        self.balls[0].position()
+    }
+
+    /// Predicts where the descending ball will reach the paddle.
+    ///
+    /// The ball's trajectory is followed down to the height at which it touches the top of
+    /// the paddle, folding in the bounces off the side walls. Bricks aren't taken into
+    /// account. With several balls in play the one to reach the paddle first is followed.
+    ///
+    /// # Returns
+    /// The x-coordinate of the ball's center at that height, or `None` if no ball is
+    /// moving down.
+    pub fn predicted_landing_x(&self) -> Option<f64> {
+        let (ball, t) = self
+            .balls
+            .iter()
+            .filter(|ball| ball.velocity().1 < 0.)
+            .map(|ball| {
+                let landing_y = self.paddle.area().top() + ball.radius();
+                (
+                    ball,
+                    ((ball.position().1 - landing_y) / -ball.velocity().1).max(0.),
+                )
+            })
+            .min_by(|(_, t1), (_, t2)| t1.total_cmp(t2))?;
+        let (x, _) = ball.position();
+        let (vx, _) = ball.velocity();
+        let radius = ball.radius();
+        let min_x = self.walls.left.area().right() + radius;
+        let max_x = self.walls.right.area().left() - radius;
+        let width = max_x - min_x;
+        if width <= 0. {
+            return Some(x);
+        }
+        // Unfold the reflections off the walls into a straight line and fold it back.
+        let unfolded = (x + vx * t - min_x).rem_euclid(2. * width);
+        Some(min_x + width - (unfolded - width).abs())
+    }
+
+    /// Suggests how to move the paddle to catch the ball, e.g. to let the game play itself.
+    ///
+    /// The paddle heads for where the descending ball will reach it, see
+    /// [`Game::predicted_landing_x`], and follows the first ball while no ball is descending.
+    ///
+    /// # Returns
+    /// The direction to move the paddle in with [`GameEvent::MovePad`], or `None` if it's
+    /// already within half a step of the target.
+    pub fn ai_suggest_move(&self) -> Option<Direction> {
+        let target = self
+            .predicted_landing_x()
+            .unwrap_or_else(|| self.ball_position().0);
+        let offset = target - self.paddle.center();
+        if offset.abs() <= self.paddle.step() / 2. {
+            None
+        } else if offset < 0. {
+            Some(Direction::Left)
+        } else {
+            Some(Direction::Right)
+        }
+    }
+
+    /// Predicts the path the ball resting on the paddle will be launched along, bouncing off
+    /// the walls but not the bricks.
+    ///
+    /// # Returns
+    /// The predicted positions of the ball's center, none if the ball isn't resting on the
+    /// paddle.
+    fn aim_line(&self) -> Vec<(f64, f64)> {
+        if !self.ball_attached {
+            return vec![];
+        }
+        let mut ball = self.balls[0].clone();
+        // The ball is launched the way the paddle last moved in, see `Game::launch`.
+        let (vx, _) = ball.velocity();
+        if self.launch_sign.is_some_and(|sign| sign * vx < 0.) {
+            ball.dvx(-2. * vx);
+        }
+        let bounds = Rectf64 {
+            x: self.walls.left.area().right(),
+            y: self.paddle.area().top(),
+            width: self.walls.right.area().left() - self.walls.left.area().right(),
+            height: self.walls.top.area().bottom() - self.paddle.area().top(),
+        };
+        ball.predict(AIM_LINE_DOTS, &bounds)
+    }
+
+    /// Returns the speed the first ball currently moves with.
+    ///
+    /// # Returns
+    /// The speed, in units per second.
+    pub fn ball_speed(&self) -> f64 {
+        self.balls[0].speed()
+    }
+
+    /// Returns the number of bricks broken since the ball last bounced off the paddle, the
+    /// multiplier of [`combo_points`].
+    pub fn combo(&self) -> usize {
+        self.combo
+    }
+
+    /// Returns the number of times the ball hit the paddle.
+    pub fn paddle_hits(&self) -> usize {
+        self.paddle_hits
+    }
+
+    /// Returns the x-coordinate of the paddle's center.
+    pub fn paddle_center(&self) -> f64 {
+        self.paddle.center()
+    }
+
+    /// Returns the most recent effects of the simulation, the oldest first.
+    #[cfg(feature = "debug")]
+    pub fn recent_events(&self) -> impl Iterator<Item = &GameEffect> {
+        self.effects.effects.iter()
+    }
+
+    /// Returns the time the game has been played for, paused time excluded.
+    ///
+    /// # Returns
+    /// The sum of the ticks' time deltas, in seconds, clamped like the simulation is.
+    pub fn elapsed_secs(&self) -> f64 {
+        self.play_time
+    }
+
+    /// Returns the number of ticks the game has been simulated for.
+    pub fn tick_count(&self) -> u64 {
+        self.tick_count
+    }
+
+    /// Returns the play time left before the time limit runs out.
+    ///
+    /// # Returns
+    /// The remaining time in seconds, or `None` if the game has no time limit.
+    pub fn time_left(&self) -> Option<f64> {
+        self.time_limit
+            .map(|limit| (limit - self.play_time).max(0.))
+    }
+
+    /// Returns the number of balls the player can still lose, the one in play included.
+    pub fn lives(&self) -> u8 {
+        self.lives
+    }
+
+    /// Returns the level being played, counted from one.
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// Returns the current score of the game.
+    pub fn score(&self) -> usize {
+        self.score
+    }
+
+    /// Sets the current score of the game.
+    ///
+    /// # Parameters
+    /// - `score`: The new score.
+    pub fn set_score(&mut self, score: usize) {
+        self.score = score;
+    }
+
+    /// Sets how the broken bricks are scored, for this level and the next ones, e.g. to
+    /// restore a custom scoring after [`Game::load`].
+    ///
+    /// # Parameters
+    /// - `scoring`: The function computing the points of a broken brick.
+    pub fn set_scoring(&mut self, scoring: Scoring) {
+        self.scoring = scoring;
+        self.level_options.scoring = scoring;
+    }
+
+    /// Carries the score of the earlier games of the session over to this one. The total is
+    /// shown next to the score of the game.
+    ///
+    /// # Parameters
+    /// - `score`: The score of the earlier games.
+    pub fn carry_score(&mut self, score: usize) {
+        self.carried_score = Some(score);
+    }
+
+    /// Returns the score of the whole session, the current game included.
+    ///
+    /// # Returns
+    /// The sum of the carried over and the current score.
+    pub fn session_score(&self) -> usize {
+        self.carried_score.unwrap_or(0).saturating_add(self.score)
+    }
+
+    /// Adds a brick to the running game. The game isn't won until it's destroyed too.
+    ///
+    /// # Parameters
+    /// - `brick`: The brick to add.
+    ///
+    /// # Returns
+    /// An error if the brick lies outside of the game area or overlaps the paddle.
+    pub fn add_brick(&mut self, brick: Brick) -> anyhow::Result<()> {
+        let area = brick.area();
+        anyhow::ensure!(
+            self.area.contains(&area),
+            "brick {area:?} lies outside of the game area"
+        );
+        anyhow::ensure!(
+            !self.paddle.area().intersects(&area),
+            "brick {area:?} overlaps the paddle"
+        );
+        self.bricks.push(brick);
+        Ok(())
+    }
+
+    /// Assembles a game from pre-built components instead of [`GameOptions::build`], e.g. to
+    /// reproduce a specific state in a test. Everything else has its default value.
+    ///
+    /// # Parameters
+    /// - `area`: The area of the playfield.
+    /// - `paddle`: The paddle.
+    /// - `balls`: The balls in play, the first one being relaunched after a life is lost.
+    /// - `walls`: The side and top walls.
+    /// - `bottom`: The bottom line the ball is lost past.
+    /// - `bricks`: The bricks to break.
+    ///
+    /// # Returns
+    /// The running game, or an error if there's no ball or more than
+    /// [`GameOptions::max_balls`], or a ball, the paddle or a brick lies outside of the game
+    /// area.
+    pub fn from_parts(
+        area: Rectf64,
+        paddle: Paddle,
+        balls: Vec<Ball>,
+        walls: Walls,
+        bottom: Bottom,
+        bricks: Vec<Brick>,
+    ) -> anyhow::Result<Game> {
+        let options = GameOptions::default().area(area.clone());
+        anyhow::ensure!(!balls.is_empty(), "the game has no ball");
+        anyhow::ensure!(
+            balls.len() <= options.max_balls,
+            "the game has {} balls, at most {} are in play at once",
+            balls.len(),
+            options.max_balls
+        );
+        for ball in &balls {
+            let (x, y) = ball.position();
+            anyhow::ensure!(
+                (area.left()..=area.right()).contains(&x)
+                    && (area.bottom()..=area.top()).contains(&y),
+                "ball at ({x}, {y}) lies outside of the game area"
+            );
+        }
+        anyhow::ensure!(
+            area.contains(&paddle.area()),
+            "paddle {:?} lies outside of the game area",
+            paddle.area()
+        );
+        if let Some(brick) = bricks.iter().find(|brick| !area.contains(&brick.area())) {
+            anyhow::bail!("brick {:?} lies outside of the game area", brick.area());
+        }
+        let mut game = options.build();
+        game.paddle = paddle;
+        game.spawn = balls[0].clone();
+        game.balls = balls;
+        game.walls = walls;
+        game.bottom = bottom;
+        game.bricks = bricks;
+        Ok(game)
+    }
+
+    /// Saves a snapshot of the game to a file, to resume it later with [`Game::load`].
+    ///
+    /// # Parameters
+    /// - `path`: The file to write the snapshot to.
+    ///
+    /// # Returns
+    /// An error if the file couldn't be written.
+    #[cfg(feature = "serde")]
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let json = serde_json::to_string(self)?;
+        std::fs::write(path, json)
+            .with_context(|| format!("failed to write the game to {}", path.display()))
+    }
+
+    /// Resumes a game from a snapshot saved with [`Game::save`]. The game's randomness is
+    /// reseeded and a custom scoring falls back to the default one, see
+    /// [`Game::set_scoring`].
+    ///
+    /// # Parameters
+    /// - `path`: The file to read the snapshot from.
+    ///
+    /// # Returns
+    /// The resumed game, or an error if the file can't be read or isn't a saved game.
+    #[cfg(feature = "serde")]
+    pub fn load(path: &Path) -> anyhow::Result<Game> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read the game from {}", path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("{} isn't a saved game", path.display()))
+    }
+
+    /// Removes all bricks from the game.
+    pub fn clear_bricks(&mut self) {
+        self.bricks.clear();
+    }
+
+    /// Processes a game event.
+    ///
+    /// # Parameters
+    /// - `game_event`: The game event to process.
+    pub fn event(&mut self, game_event: GameEvent) {
+        if self.state != GameState::Running {
+            return;
+        }
+
+        match game_event {
+            GameEvent::MovePad { direction } => {
+                let sign = match direction {
+                    Direction::Left => {
+                        self.paddle.mov(Direction::Left);
+                        -1.
+                    }
+                    Direction::Right => {
+                        self.paddle.mov(Direction::Right);
+                        1.
+                    }
+                    #[cfg(feature = "debug")]
+                    _ => unreachable!(),
+                };
+                self.carry_ball(sign);
+            }
+            GameEvent::MovePadTo { x } => {
+                let sign = (x - self.paddle.center()).signum();
+                self.paddle.mov_to(x);
+                self.carry_ball(sign);
+            }
+            GameEvent::Launch => {
+                self.launch();
+            }
+            #[cfg(feature = "debug")]
+            GameEvent::MoveBallManual { direction } => {
+                self.balls[0].mov_dir(direction);
+                self.check_collisions();
+            }
+            GameEvent::Tick { dt } => {
+                self.tick_count += 1;
+                if self.demo {
+                    self.play_demo(dt);
+                }
+                self.move_ball(dt.min(self.max_frame_delta));
+            }
+        }
+    }
+
+    /// Advances the cosmetic animations, e.g. fading score popups. Unlike the simulation they
+    /// keep playing while the game is paused or over.
+    ///
+    /// # Parameters
+    /// - `dt`: The time delta, in seconds.
+    pub fn animate(&mut self, dt: f64) {
+        self.popups.iter_mut().for_each(|popup| popup.ttl -= dt);
+        self.popups.retain(|popup| popup.ttl > 0.);
+        self.paddle.fade(dt);
+        self.banner = (self.banner - dt).max(0.);
+    }
+
+    /// Advances the idle animations while the simulation is frozen, e.g. paused.
+    ///
+    /// # Parameters
+    /// - `dt`: The time delta, in seconds.
+    pub fn idle(&mut self, dt: f64) {
+        if !self.brick_pulse {
+            return;
+        }
+        self.pulse_phase = (self.pulse_phase + dt) % PULSE_PERIOD;
+        let glow = 0.8 + 0.2 * (self.pulse_phase / PULSE_PERIOD * std::f64::consts::TAU).cos();
+        self.bricks
+            .iter_mut()
+            .for_each(|brick| brick.set_glow(glow));
+    }
+
+    /// Moves the ball and checks for collisions.
+    ///
+    /// # Parameters
+    /// - `dt`: The time delta for the movement, in seconds.
+    fn move_ball(&mut self, dt: f64) {
+        if self.pulse_phase != 0. {
+            self.pulse_phase = 0.;
+            self.bricks.iter_mut().for_each(|brick| brick.set_glow(1.));
+        }
+
+        // While the lost ball is flashing the rest of the simulation is paused.
+        if let Some(remaining) = self.dying {
+            let remaining = remaining - dt;
+            if remaining > 0. {
+                self.dying = Some(remaining);
+            } else {
+                self.dying = None;
+                self.lose_life();
+            }
+        } else if !self.ball_attached {
+            for ball in &mut self.balls {
+                ball.mov(dt);
+                if self.fog {
+                    self.bricks
+                        .iter_mut()
+                        .for_each(|brick| brick.reveal_near(ball, REVEAL_RADIUS));
+                }
+            }
+            if self.ball_collisions {
+                // There's only a handful of balls, checking every pair of them is cheap.
+                for i in 1..self.balls.len() {
+                    let (others, rest) = self.balls.split_at_mut(i);
+                    for other in others {
+                        rest[0].collide_with(other);
+                    }
+                }
+            }
+            self.check_collisions();
+            self.invade(dt);
+            self.drop_powerups(dt);
+            if let Some(max_airtime) = self.ball_max_airtime {
+                for ball in &mut self.balls {
+                    if ball.airtime() >= max_airtime {
+                        ball.reset_airtime();
+                        nudge_ball(ball);
+                    }
+                }
+            }
+        }
+
+        self.play_time += dt;
+        if self.state == GameState::Running && self.time_left() == Some(0.) {
+            self.state = GameState::TimeUp;
+        }
+    }
+
+    /// Takes a life for the lost ball and relaunches it from the paddle, or ends the game if
+    /// it was the last one.
+    fn lose_life(&mut self) {
+        self.lives = self.lives.saturating_sub(1);
+        if self.lives == 0 {
+            self.state = GameState::Lost;
+            return;
+        }
+        self.deaths = self.deaths.saturating_add(1);
+        // The power-ups last for a life at most.
+        self.restore_paddle();
+        self.relaunch();
+    }
+
+    /// Moves the ball resting on the paddle along with it.
+    ///
+    /// # Parameters
+    /// - `sign`: The horizontal direction, `-1.0` or `1.0`, the paddle moved in, `0.0` if it
+    ///   didn't move.
+    fn carry_ball(&mut self, sign: f64) {
+        if !self.ball_attached {
+            return;
+        }
+        for ball in &mut self.balls {
+            ball.place(
+                self.paddle.center() - ball.radius(),
+                self.paddle.collision_area().top() + ball.radius(),
+            );
+        }
+        if sign != 0. {
+            self.launch_sign = Some(sign);
+        }
+    }
+
+    /// Launches the ball resting on the paddle, in the direction the paddle last moved in.
+    fn launch(&mut self) {
+        if !self.ball_attached {
+            return;
+        }
+        self.ball_attached = false;
+        let sign = self.launch_sign.take();
+        for ball in &mut self.balls {
+            let (vx, _) = ball.velocity();
+            if sign.is_some_and(|sign| sign * vx < 0.) {
+                ball.dvx(-2. * vx);
+            }
+        }
+    }
+
+    /// Plays the demo for a step: launches the ball resting on the paddle and moves the paddle
+    /// every [`DEMO_MOVE_INTERVAL`] the way [`Game::ai_suggest_move`] suggests.
+    ///
+    /// # Parameters
+    /// - `dt`: The time delta, in seconds.
+    fn play_demo(&mut self, dt: f64) {
+        self.launch();
+        self.demo_wait -= dt;
+        if self.demo_wait > 0. {
+            return;
+        }
+        self.demo_wait = DEMO_MOVE_INTERVAL;
+        if let Some(direction) = self.ai_suggest_move() {
+            self.event(GameEvent::MovePad { direction });
+        }
+    }
+
+    /// Launches the ball from where the paddle is now, like at the start of the game.
+    fn relaunch(&mut self) {
+        let mut ball = self.spawn.clone();
+        ball.place(
+            self.paddle.center() - ball.radius(),
+            self.paddle.collision_area().top() + ball.radius(),
+        );
+        self.balls.clear();
+        self.spawn_ball(ball);
+        self.combo = 0;
+        self.ball_attached = self.sticky_launch;
+        self.launch_sign = None;
+    }
+
+    /// Lays out the bricks of the next level and relaunches a faster ball from the paddle.
+    fn advance_level(&mut self) {
+        self.level += 1;
+        let extra = u16::from(self.level - 1);
+        let mut options = self
+            .level_options
+            .clone()
+            .brick_count(
+                self.level_options
+                    .brick_count
+                    .saturating_add(extra.saturating_mul(LEVEL_BRICKS)),
+            )
+            .ball_speed(self.level_options.ball_speed * LEVEL_SPEEDUP.powi(extra.into()));
+        // A generated or seeded layout would otherwise repeat itself.
+        options.generator = options
+            .generator
+            .map(|(seed, difficulty)| (seed.wrapping_add(extra.into()), difficulty));
+        options.seed = options.seed.map(|seed| seed.wrapping_add(extra.into()));
+        let next = options.build();
+        self.bricks = next.bricks;
+        self.spawn = next.spawn;
+        self.max_ball_speed = next.max_ball_speed;
+        self.deaths = 0;
+        if self.resized.is_none() {
+            self.restore_paddle();
+        }
+        self.relaunch();
+        self.banner = LEVEL_BANNER;
+    }
+
+    /// Moves the falling power-ups down, applying the ones caught with the paddle and
+    /// discarding the ones that reached the bottom.
+    ///
+    /// # Parameters
+    /// - `dt`: The time delta, in seconds.
+    fn drop_powerups(&mut self, dt: f64) {
+        if let Some(remaining) = self.resized {
+            if remaining > dt {
+                self.resized = Some(remaining - dt);
+            } else {
+                self.restore_paddle();
+            }
+        }
+
+        let bottom = self.bottom.area().top();
+        let mut caught = vec![];
+        self.powerups.retain_mut(|powerup| {
+            powerup.fall(dt);
+            if powerup.area().intersects(&self.paddle.area()) {
+                caught.push(powerup.kind());
+                return false;
+            }
+            powerup.area().bottom() > bottom
+        });
+        for kind in caught {
+            self.apply_powerup(kind);
+        }
+    }
+
+    /// Applies the effect of a caught power-up.
+    ///
+    /// # Parameters
+    /// - `kind`: The effect of the power-up.
+    fn apply_powerup(&mut self, kind: PowerUpKind) {
+        match kind {
+            PowerUpKind::WidePaddle => self.resize_paddle(WIDE_PADDLE),
+            PowerUpKind::NarrowPaddle => self.resize_paddle(NARROW_PADDLE),
+            PowerUpKind::SlowBall => self
+                .balls
+                .iter_mut()
+                .for_each(|ball| ball.slow_down(SLOW_BALL)),
+            PowerUpKind::ExtraLife => self.lives = self.lives.saturating_add(1),
+            PowerUpKind::MultiBall => self.split_balls(),
+        }
+    }
+
+    /// Splits every ball in play in two, the new one mirrored horizontally, or turned aside if
+    /// the ball moves vertically.
+    fn split_balls(&mut self) {
+        let mirrored: Vec<_> = self
+            .balls
+            .iter()
+            .map(|ball| {
+                let mut ball = ball.clone();
+                let (vx, _) = ball.velocity();
+                if vx == 0. {
+                    ball.rotate(SPLIT_ANGLE);
+                } else {
+                    ball.dvx(-2. * vx);
+                }
+                ball
+            })
+            .collect();
+        for ball in mirrored {
+            self.spawn_ball(ball);
+        }
+    }
+
+    /// Puts another ball in play, unless there are already as many balls as allowed.
+    ///
+    /// # Parameters
+    /// - `ball`: The ball to put in play.
+    ///
+    /// # Returns
+    /// `true` if the ball was put in play, `false` if the limit was reached.
+    pub fn spawn_ball(&mut self, ball: Ball) -> bool {
+        if self.balls.len() >= self.max_balls {
+            return false;
+        }
+        self.balls.push(ball);
+        true
+    }
+
+    /// Resizes the paddle for a while, replacing an earlier resize. The paddle keeps its
+    /// center, so the ball resting on it stays put.
+    ///
+    /// # Parameters
+    /// - `factor`: The factor the paddle's original width is scaled by.
+    fn resize_paddle(&mut self, factor: f64) {
+        self.paddle.set_width(self.paddle.base_width() * factor);
+        self.resized = Some(self.powerup_duration);
+        self.carry_ball(0.);
+    }
+
+    /// Gives the paddle back its original width, widened in mercy mode for the lives lost on
+    /// the level.
+    fn restore_paddle(&mut self) {
+        let factor = if self.mercy {
+            (1. + MERCY_WIDENING * f64::from(self.deaths)).min(MERCY_MAX_WIDTH)
+        } else {
+            1.
+        };
+        self.paddle.set_width(self.paddle.base_width() * factor);
+        self.resized = None;
+    }
+
+    /// Moves the bricks towards the paddle in invasion mode.
+    ///
+    /// # Parameters
+    /// - `dt`: The time delta, in seconds.
+    fn invade(&mut self, dt: f64) {
+        let Some(invasion) = self.invasion.as_mut() else {
+            return;
+        };
+        if self.state != GameState::Running {
+            return;
+        }
+        invasion.timer += dt;
+        if invasion.timer < INVASION_INTERVAL {
+            return;
+        }
+        invasion.timer -= INVASION_INTERVAL;
+
+        self.bricks
+            .iter_mut()
+            .for_each(|brick| brick.shift_down(self.brick_height));
+        for area in &invasion.spawn_row {
+            if self.rng.gen_bool(0.5) {
+                self.bricks.push(
+                    Brick::new(area.clone())
+                        .splitting(self.split_depth)
+                        .hidden(self.fog),
+                );
+            }
+        }
+
+        // The bricks reached the paddle - the game is lost.
+        let paddle_top = self.paddle.area().top();
+        if self
+            .bricks
+            .iter()
+            .any(|brick| brick.area().bottom() <= paddle_top)
+        {
+            self.state = GameState::Lost;
+        }
+    }
+
+    /// Checks for collisions and updates the game state accordingly.
+    ///
+    /// TODO: maybe I need to predict collisions
+    /// instead of acting upon them, but for now
+    /// this implementation is ok.
+    pub fn check_collisions(&mut self) {
+        if self.dying.is_some() {
+            return;
+        }
+
+        let mut balls = std::mem::take(&mut self.balls);
+        let mut splits = vec![];
+        let lost: Vec<bool> = balls
+            .iter_mut()
+            .map(|ball| self.collide_ball(ball, &mut splits))
+            .collect();
+        self.balls = balls;
+        // Once the last ball is lost a life is lost, after the loss animation flashing it.
+        if self.remove_lost_balls(&lost) {
+            #[cfg(feature = "debug")]
+            self.effects.push(GameEffect::BallLost);
+            if self.death_animation > 0. {
+                self.dying = Some(self.death_animation);
+            } else {
+                self.lose_life();
+            }
+            return;
+        }
+        for ball in splits {
+            self.spawn_ball(ball);
+        }
+
+        // If no bricks left - the level is cleared, and after the last one the game is won.
+        if self.bricks.is_empty() {
+            if self.level < self.level_count {
+                self.advance_level();
+            } else {
+                self.state = GameState::Won;
+            }
+        }
+    }
+
+    /// Takes the balls that left the playfield out of play. The last ball stays in play, for
+    /// the loss animation to flash it.
+    ///
+    /// # Parameters
+    /// - `lost`: Whether each of the balls left the playfield.
+    ///
+    /// # Returns
+    /// `true` if all of the balls were lost, `false` otherwise.
+    fn remove_lost_balls(&mut self, lost: &[bool]) -> bool {
+        if lost.iter().all(|&lost| lost) {
+            return true;
+        }
+        let mut lost = lost.iter();
+        self.balls
+            .retain(|_| !lost.next().copied().unwrap_or_default());
+        false
+    }
+
+    /// Checks for the collisions of a single ball and updates the game state accordingly.
+    ///
+    /// # Parameters
+    /// - `ball`: The ball to check, taken out of the game.
+    /// - `splits`: The balls split off by the wall bounces, to put in play afterwards.
+    ///
+    /// # Returns
+    /// `true` if the ball is lost, `false` otherwise.
+    fn collide_ball(&mut self, ball: &mut Ball, splits: &mut Vec<Ball>) -> bool {
+        // Process ball collision with the walls and the paddle.
+        for wall in [&self.walls.left, &self.walls.right, &self.walls.top] {
+            let heading = ball.velocity();
+            if ball.collision(wall) {
+                #[cfg(feature = "debug")]
+                self.effects.push(GameEffect::WallBounce);
+                // Only a ball actually bouncing splits, not one still touching the wall after.
+                if ball.velocity() != heading
+                    && self.wall_split_chance > 0.
+                    && self.rng.gen_bool(self.wall_split_chance)
+                {
+                    let angle = self.rng.gen_range(0.0..=WALL_SPLIT_SPREAD);
+                    let mut split = ball.clone();
+                    ball.rotate(angle);
+                    split.rotate(-angle);
+                    splits.push(split);
+                }
+            }
+        }
+        if self.restitution > 1. {
+            ball.cap_speed(self.max_ball_speed);
+        }
+        if ball.collision(&self.paddle) {
+            // Reward catching the ball with the very edge of the paddle.
+            let (x, y) = ball.position();
+            self.combo = 0;
+            ball.reset_airtime();
+            ball.set_on_fire(false);
+            self.paddle_hits += 1;
+            // The paddle makes up for the speed lost bouncing around.
+            if self.restitution < 1. {
+                let (vx, vy) = self.spawn.velocity();
+                let (bx, by) = ball.velocity();
+                ball.accelerate(vx.hypot(vy) - bx.hypot(by), self.max_ball_speed);
+            }
+            if self.speed_ramp > 0. {
+                ball.accelerate(self.speed_ramp, self.max_ball_speed);
+            }
+            self.paddle.hit(self.paddle.hit_offset(x));
+            #[cfg(feature = "debug")]
+            self.effects.push(GameEffect::PaddleBounce {
+                offset: self.paddle.hit_offset(x),
+            });
+            if self.paddle.hit_offset(x).abs() >= EDGE_ZONE {
+                self.score = self.score.saturating_add(EDGE_BONUS);
+                self.popups.push(ScorePopup {
+                    text: format!("+{EDGE_BONUS}"),
+                    position: (x, y + ball.radius()),
+                    ttl: POPUP_TTL,
+                });
+            }
+        }
+
+        // Move the ball and check if it possibly
+        // fell down or escaped through a gap in the top wall.
+        let fell = ball.collision(&self.bottom) && !self.bottom.bounces();
+        if fell || ball.position().1 > self.area.top() {
+            return true;
+        }
+
+        // Check if the ball collided with any of the "closest" bricks and if it did - remove those.
+        self.bricks.iter_mut().for_each(|brick| brick.settle(ball));
+        self.bricks
+            .sort_by(|b1, b2| ball.dsquared(b1).total_cmp(&ball.dsquared(b2)));
+        let (closest, mut other): (Vec<_>, Vec<_>) = std::mem::take(&mut self.bricks)
+            .into_iter()
+            .partition(|brick| ball.collision(brick));
+        let hit = !closest.is_empty();
+        // Only the destroyed bricks score, the damaged ones stay in place.
+        let (closest, damaged): (Vec<_>, Vec<_>) = closest
+            .into_iter()
+            .map(|mut brick| {
+                // A ball on fire breaks the multi-hit bricks outright.
+                if ball.is_on_fire() {
+                    brick.shatter();
+                    (true, brick)
+                } else {
+                    (brick.hit(), brick)
+                }
+            })
+            .partition(|(destroyed, _)| *destroyed);
+        let closest: Vec<_> = closest.into_iter().map(|(_, brick)| brick).collect();
+        other.extend(damaged.into_iter().map(|(_, brick)| brick));
+        for brick in &closest {
+            if self.powerup_chance > 0. && self.rng.gen_bool(self.powerup_chance) {
+                let kind = *PowerUpKind::ALL.choose(&mut self.rng).unwrap();
+                let area = brick.area();
+                self.powerups.push(PowerUp::new(
+                    kind,
+                    (area.x + area.width / 2., area.y + area.height / 2.),
+                ));
+            }
+            self.combo += 1;
+            let (vx, vy) = ball.velocity();
+            let points = (self.scoring)(&BrickBreak {
+                row: ((self.area.top() - brick.area().top()) / self.brick_height).max(0.) as usize,
+                combo: self.combo,
+                ball_speed: vx.hypot(vy),
+                hp: brick.hp(),
+                full_hp: brick.full_hp(),
+            });
+            self.score = self.score.saturating_add(points);
+        }
+        if hit {
+            ball.reset_airtime();
+            if self.restitution > 1. {
+                ball.cap_speed(self.max_ball_speed);
+            }
+        }
+        if !closest.is_empty() {
+            ball.accelerate(
+                closest.len() as f64 * self.speed_per_brick,
+                self.max_ball_speed,
+            );
+        }
+        if self.fire_streak > 0 && self.combo >= self.fire_streak {
+            ball.set_on_fire(true);
+        }
+        #[cfg(feature = "debug")]
+        if !closest.is_empty() {
+            self.effects.push(GameEffect::BrickBreak {
+                count: closest.len(),
+            });
+        }
+        if hit && self.bounce_jitter > 0. {
+            let angle = self.rng.gen_range(-self.bounce_jitter..=self.bounce_jitter);
+            ball.rotate(angle);
+        }
+        // Splitting bricks leave their halves behind.
+        other.extend(closest.iter().flat_map(Brick::split));
+
+        // Drop the bricks above the cleared rows, the highest row first so the bricks above
+        // several cleared rows drop once for each.
+        if self.compaction {
+            let mut cleared: Vec<f64> = closest
+                .iter()
+                .map(|brick| brick.area().y)
+                .filter(|&y| other.iter().all(|brick| brick.area().y != y))
+                .collect();
+            cleared.sort_by(|y1, y2| y2.total_cmp(y1));
+            cleared.dedup();
+            for y in cleared {
+                other
+                    .iter_mut()
+                    .filter(|brick| brick.area().y > y)
+                    .for_each(|brick| brick.shift_down(self.brick_height));
+            }
+        }
+
+        std::mem::swap(&mut self.bricks, &mut other);
+        false
+    }
+}
+
+/// Picks the color of the countdown, warning as the time runs low.
+///
+/// # Parameters
+/// - `left`: The play time left, in seconds.
+/// - `limit`: The time limit, in seconds.
+///
+/// # Returns
+/// Red once under [`TIME_ALERT`] of the limit is left, yellow under [`TIME_WARNING`], white
+/// otherwise.
+fn countdown_color(left: f64, limit: f64) -> Color {
+    if left <= limit * TIME_ALERT {
+        Color::LightRed
+    } else if left <= limit * TIME_WARNING {
+        Color::LightYellow
+    } else {
+        Color::White
+    }
+}
+
+impl Game {
+    /// Draws the game with the given renderer.
+    ///
+    /// # Parameters
+    /// - `renderer`: The renderer to draw the game with.
+    pub fn render<R: GameRenderer>(&self, renderer: &mut R) {
+        // Without colors the background can't be dimmed and would clutter the playfield.
+        if let Some(text) = self
+            .background
+            .as_ref()
+            .filter(|_| self.color_mode == ColorMode::Full)
+        {
+            let factor = 24.0;
+            let width = text.chars().count() as f64 * factor;
+            Word::new(
+                text.to_lowercase(),
+                (
+                    self.area.x + (self.area.width - width).max(0.) / 2.,
+                    self.area.y + self.area.height * 0.6,
+                ),
+                factor,
+                Color::White,
+            )
+            .dimmed()
+            .render(renderer);
+        }
+
+        self.walls.render(renderer);
+        self.paddle.render(renderer);
+        if let Some(x) = self
+            .predicted_landing_x()
+            .filter(|_| self.aim_assist && self.dying.is_none())
+        {
+            let y = self.paddle.area().top();
+            renderer.draw_line(
+                x,
+                y + 1.,
+                x,
+                y + 5.,
+                self.color_mode.apply(Color::LightCyan),
+            );
+        }
+        if self.aim_assist {
+            for (x, y) in self.aim_line() {
+                renderer.draw_point(x, y, self.color_mode.apply(Color::LightCyan));
+            }
+        }
+        match self.dying {
+            // Flash the lost balls and the bottom line a few times a second.
+            Some(remaining) => {
+                if ((remaining * 8.) as u32 & 1) == 0 {
+                    self.balls
+                        .iter()
+                        .for_each(|ball| ball.draw_in(renderer, self.color_mode));
+                    self.bottom.render(renderer);
+                }
+            }
+            None => self
+                .balls
+                .iter()
+                .for_each(|ball| ball.draw_in(renderer, self.color_mode)),
+        }
+        self.bricks
+            .iter()
+            .for_each(|brick| brick.draw_in(renderer, self.color_mode, &self.brick_style));
+        self.powerups
+            .iter()
+            .for_each(|powerup| powerup.draw_in(renderer, self.color_mode));
+        self.popups.iter().for_each(|popup| {
+            Word::new(popup.text.clone(), popup.position, 5.0, Color::LightCyan)
+                .in_mode(self.color_mode)
+                .render(renderer)
+        });
+
+        if self.banner > 0. && self.state == GameState::Running {
+            Word::new(
+                format!("level {}", self.level),
+                (
+                    self.area.x + self.area.width * 0.38,
+                    self.area.y + self.area.height * 0.4,
+                ),
+                12.0,
+                Color::LightCyan,
+            )
+            .in_mode(self.color_mode)
+            .render(renderer);
+        }
+
+        match &self.state {
+            GameState::Lost => {
+                Word::new(
+                    "game over".to_string(),
+                    (
+                        self.area.x + self.area.width * 0.35,
+                        self.area.y + self.area.height / 2.,
+                    ),
+                    12.0,
+                    Color::Red,
+                )
+                .in_mode(self.color_mode)
+                .render(renderer);
+            }
+            GameState::Won => {
+                Word::new(
+                    "you won".to_string(),
+                    (
+                        self.area.x + self.area.width * 0.35,
+                        self.area.y + self.area.height / 2.,
+                    ),
                     12.0,
                     Color::LightGreen,
                 )
-                .draw(painter);
+                .in_mode(self.color_mode)
+                .render(renderer);
+            }
+            GameState::TimeUp => {
+                Word::new(
+                    "time up".to_string(),
+                    (
+                        self.area.x + self.area.width * 0.35,
+                        self.area.y + self.area.height / 2.,
+                    ),
+                    12.0,
+                    Color::LightYellow,
+                )
+                .in_mode(self.color_mode)
+                .render(renderer);
+            }
+            GameState::Running => {}
+        }
+
+        // The ball icons take the top-right corner, so the countdown moves over to where the
+        // number of lives would be.
+        let icons = self.lives_display == LivesDisplay::Icons && self.lives <= MAX_LIFE_ICONS;
+        if let (Some(left), Some(limit)) = (self.time_left(), self.time_limit) {
+            let color = countdown_color(left, limit);
+            let left = left.ceil() as u64;
+            Word::new(
+                format!("time: {}:{:02}", left / 60, left % 60),
+                (
+                    self.area.x + self.area.width * if icons { 0.5 } else { 0.75 },
+                    self.area.y + self.area.height * 0.95,
+                ),
+                7.0,
+                color,
+            )
+            .in_mode(self.color_mode)
+            .render(renderer);
+        }
+
+        if icons {
+            let y = self.area.y + self.area.height * 0.95 + LIFE_ICON_RADIUS;
+            for i in 0..self.lives {
+                let x = self.area.right() - LIFE_ICON_SPACING * (f64::from(i) + 1.);
+                ball::draw_glow(
+                    renderer,
+                    (x, y),
+                    LIFE_ICON_RADIUS,
+                    self.color_mode.apply(Color::LightRed),
+                );
+            }
+        } else {
+            Word::new(
+                format!("lives: {}", self.lives()),
+                (
+                    self.area.x + self.area.width * 0.5,
+                    self.area.y + self.area.height * 0.95,
+                ),
+                7.0,
+                Color::White,
+            )
+            .in_mode(self.color_mode)
+            .render(renderer);
+        }
+
+        let score = match self.carried_score {
+            Some(_) => format!("score: {} total: {}", self.score, self.session_score()),
+            None => format!("score: {}", self.score),
+        };
+        Word::new(
+            score,
+            (
+                self.area.x + self.area.width * 0.01,
+                self.area.y + self.area.height * 0.95,
+            ),
+            7.0,
+            Color::White,
+        )
+        .in_mode(self.color_mode)
+        .render(renderer);
+    }
+}
+
+#[cfg(feature = "tui")]
+impl Shape for Game {
+    fn draw(&self, painter: &mut Painter) {
+        self.render(painter);
+    }
+}
+
+/// Turns the ball down towards the paddle, keeping its speed and horizontal direction, unless
+/// it's already heading down steeply.
+///
+/// # Parameters
+/// - `ball`: The ball to turn.
+fn nudge_ball(ball: &mut Ball) {
+    let (vx, vy) = ball.velocity();
+    let target = -NUDGE_ANGLE;
+    let current = vy.atan2(vx.abs());
+    if current > target {
+        // Mirror the rotation for a ball moving to the left.
+        let angle = target - current;
+        ball.rotate(if vx < 0. { -angle } else { angle });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::prelude::Rect;
+
+    fn game() -> Game {
+        GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(48.)
+            .brick_count(10)
+            .build()
+    }
+
+    /// Replaces the bricks of the game with a single one in the middle of the brick area.
+    fn single_brick(game: &mut Game) -> Rectf64 {
+        let area = Rectf64 {
+            x: 170.,
+            y: 120.,
+            width: BRICK_WIDTH,
+            height: BRICK_HEIGHT,
+        };
+        game.bricks = vec![Brick::new(area.clone())];
+        area
+    }
+
+    #[test]
+    fn test_force_state_stops_the_game() {
+        for state in [GameState::Lost, GameState::Won, GameState::TimeUp] {
+            let mut game = game();
+            let position = game.balls[0].position();
+            game.force_state(state.clone());
+            game.event(GameEvent::Tick { dt: 0.1 });
+            assert_eq!(game.state, state);
+            assert_eq!(game.balls[0].position(), position);
+        }
+    }
+
+    #[test]
+    fn test_move_pad_to_is_clamped() {
+        let mut game = game();
+        let half_width = 360. / 10. / 2.;
+
+        game.event(GameEvent::MovePadTo { x: -100. });
+        assert_eq!(game.paddle_center(), WALL_W + half_width);
+
+        game.event(GameEvent::MovePadTo { x: 500. });
+        assert_eq!(game.paddle_center(), 360. - WALL_W - half_width);
+
+        game.event(GameEvent::MovePadTo { x: 100. });
+        assert_eq!(game.paddle_center(), 100.);
+    }
+
+    #[test]
+    fn test_speed_ramp() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(30.)
+            .brick_count(10)
+            .speed_ramp(5.)
+            .max_ball_speed(55.)
+            // Keep the paddle's spin out of the ball's speed.
+            .paddle_bounce_angle(paddle::MAX_BOUNCE_ANGLE)
+            .death_animation(0.)
+            .build();
+        let start = game.ball_speed();
+        let paddle = game.paddle.area();
+        let catch = |game: &mut Game| {
+            game.balls = vec![Ball::new(
+                paddle.x + paddle.width / 2.,
+                paddle.top() + 2.,
+                3.,
+                0.,
+                -30.,
+            )];
+            game.check_collisions();
+        };
+        catch(&mut game);
+        assert_eq!(game.paddle_hits(), 1);
+        assert!((game.ball_speed() - 35.).abs() < 1e-9);
+        // The direction is kept.
+        let (vx, vy) = game.balls[0].velocity();
+        assert!(vx.abs() < 1e-9 && vy > 0.);
+
+        // The cap holds however long the ball stays in play.
+        for _ in 0..10 {
+            let mut ball = game.balls[0].clone();
+            ball.place(paddle.x + paddle.width / 2., paddle.top() + 2.);
+            ball.bouncev();
+            game.balls = vec![ball];
+            game.check_collisions();
+        }
+        assert_eq!(game.paddle_hits(), 11);
+        assert!((game.ball_speed() - 55.).abs() < 1e-9);
+
+        // A new ball starts at the initial speed again.
+        game.balls = vec![Ball::new(20., 1., 3., 0., -30.)];
+        game.check_collisions();
+        assert!((game.ball_speed() - start).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ai_suggest_move() {
+        let mut game = game();
+        let center = game.paddle_center();
+        // A descending ball is met where it lands.
+        game.balls = vec![Ball::new(center + 60., 100., 3., 0., -48.)];
+        assert!(matches!(game.ai_suggest_move(), Some(Direction::Right)));
+        game.balls = vec![Ball::new(center - 60., 100., 3., 0., -48.)];
+        assert!(matches!(game.ai_suggest_move(), Some(Direction::Left)));
+        game.balls = vec![Ball::new(center + 2., 100., 3., 0., -48.)];
+        assert!(game.ai_suggest_move().is_none());
+        // A rising one is followed.
+        game.balls = vec![Ball::new(center - 60., 100., 3., 48., 48.)];
+        assert!(matches!(game.ai_suggest_move(), Some(Direction::Left)));
+    }
+
+    #[test]
+    fn test_ai_keeps_the_ball_in_play() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(48.)
+            .brick_count(10)
+            .paddle_bounce_angle(paddle::MAX_BOUNCE_ANGLE)
+            .seed(3)
+            .build();
+        let lives = game.lives();
+        for tick in 0..24 * 60 {
+            if tick % 5 == 0 {
+                if let Some(direction) = game.ai_suggest_move() {
+                    game.event(GameEvent::MovePad { direction });
+                }
             }
-            _ => {}
+            game.event(GameEvent::Tick { dt: 1. / 120. });
         }
+        assert_eq!(game.lives(), lives);
+    }
 
-        Word::new(
-            format!("score: {}", self.score),
-            (
-                self.area.x + self.area.width * 0.01,
-                self.area.y + self.area.height * 0.95,
-            ),
-            7.0,
-            Color::White,
-        )
-        .draw(painter);
+    #[test]
+    fn test_demo_plays_itself() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(48.)
+            .brick_count(10)
+            .paddle_bounce_angle(paddle::MAX_BOUNCE_ANGLE)
+            .sticky_launch(true)
+            .seed(3)
+            .demo(true)
+            .build();
+        let lives = game.lives();
+        for _ in 0..24 * 60 {
+            game.event(GameEvent::Tick { dt: 1. / 120. });
+        }
+        // The ball is launched without a `GameEvent::Launch` and kept in play.
+        assert!(!game.ball_attached);
+        assert!(game.paddle_hits() > 0);
+        assert_eq!(game.lives(), lives);
+    }
+
+    #[test]
+    fn test_launch_at_the_step_rate_leaves_the_paddle() {
+        let mut game = game();
+        let lives = game.lives();
+        let dt = 1. / f64::from(crate::timestep::STEP_RATE);
+        for _ in 0..60 {
+            game.event(GameEvent::Tick { dt });
+        }
+        // Half a second in, the ball is well above the paddle, never bounced back down.
+        assert!(game.ball_position().1 > game.paddle.area().top() + 20.);
+        assert_eq!(game.paddle_hits(), 0);
+        assert_eq!(game.lives(), lives);
+        assert_eq!(game.score(), 0);
+    }
+
+    #[test]
+    fn test_initial_direction() {
+        let launch = |direction| {
+            GameOptions::default()
+                .area(Rect::new(0, 0, 360, 180).into())
+                .ball_speed(48.)
+                .initial_direction(direction)
+                .build()
+                .balls[0]
+                .velocity()
+        };
+        assert_eq!(launch(InitialDir::UpLeft), (-48., 48.));
+        assert_eq!(launch(InitialDir::UpRight), (48., 48.));
+        let (vx, vy) = launch(InitialDir::Random);
+        assert_eq!((vx.abs(), vy), (48., 48.));
+    }
+
+    #[test]
+    fn test_time_limit() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(48.)
+            .brick_count(10)
+            .time_limit(0.25)
+            .build();
+        single_brick(&mut game);
+
+        game.event(GameEvent::Tick { dt: 0.1 });
+        game.event(GameEvent::Tick { dt: 0.1 });
+        assert_eq!(game.state, GameState::Running);
+        assert!((game.time_left().unwrap() - 0.05).abs() < 1e-9);
+
+        game.event(GameEvent::Tick { dt: 0.1 });
+        assert_eq!(game.state, GameState::TimeUp);
+        assert_eq!(game.time_left(), Some(0.));
+
+        // The game doesn't tick anymore.
+        let position = game.balls[0].position();
+        game.event(GameEvent::Tick { dt: 0.1 });
+        assert_eq!(game.balls[0].position(), position);
+    }
+
+    #[test]
+    fn test_elapsed_and_tick_count() {
+        let mut game = game();
+        assert_eq!((game.elapsed_secs(), game.tick_count()), (0., 0));
+        for _ in 0..3 {
+            game.event(GameEvent::Tick { dt: 0.05 });
+        }
+        assert!((game.elapsed_secs() - 0.15).abs() < 1e-9);
+        assert_eq!(game.tick_count(), 3);
+
+        // While paused the game only idles.
+        game.idle(1.);
+        game.animate(1.);
+        assert!((game.elapsed_secs() - 0.15).abs() < 1e-9);
+        assert_eq!(game.tick_count(), 3);
+
+        // Long frames are clamped.
+        game.event(GameEvent::Tick { dt: 5. });
+        assert!((game.elapsed_secs() - 0.15 - MAX_FRAME_DELTA).abs() < 1e-9);
+        assert_eq!(game.tick_count(), 4);
+    }
+
+    #[test]
+    fn test_bottom_bounces() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(10)
+            .bottom_bounces(true)
+            .build();
+        // The ball misses the paddle, far on the left of it.
+        game.balls = vec![Ball::new(20., 4., 3., 0., -40.)];
+        game.check_collisions();
+        assert_eq!(game.state(), GameState::Running);
+        assert_eq!(game.balls[0].velocity(), (0., 40.));
+    }
+
+    #[test]
+    fn test_countdown_color() {
+        assert_eq!(countdown_color(60., 60.), Color::White);
+        assert_eq!(countdown_color(10.5, 60.), Color::White);
+        assert_eq!(countdown_color(10., 60.), Color::LightYellow);
+        assert_eq!(countdown_color(3.5, 60.), Color::LightYellow);
+        assert_eq!(countdown_color(3., 60.), Color::LightRed);
+        assert_eq!(countdown_color(0., 60.), Color::LightRed);
+        // The thresholds scale with the limit.
+        assert_eq!(countdown_color(25., 120.), Color::White);
+        assert_eq!(countdown_color(10., 120.), Color::LightYellow);
+        assert_eq!(countdown_color(6., 120.), Color::LightRed);
+    }
+
+    #[test]
+    fn test_carried_score() {
+        let mut first = game();
+        first.set_score(5);
+        assert_eq!(first.score(), 5);
+        assert_eq!(first.session_score(), 5);
+
+        // Restarting carries the session total over.
+        let mut restarted = game();
+        restarted.carry_score(first.session_score());
+        restarted.set_score(3);
+        assert_eq!(restarted.score(), 3);
+        assert_eq!(restarted.session_score(), 8);
+    }
+
+    #[test]
+    fn test_starting_score_saturates() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(10)
+            .starting_score(usize::MAX - 1)
+            .scoring(|_| 1000)
+            .build();
+        assert_eq!(game.score(), usize::MAX - 1);
+        let brick = single_brick(&mut game);
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 2.,
+            3.,
+            30.,
+            40.,
+        )];
+        game.check_collisions();
+        assert_eq!(game.score(), usize::MAX);
+
+        game.carry_score(10);
+        assert_eq!(game.session_score(), usize::MAX);
+    }
+
+    #[test]
+    fn test_custom_scoring() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(10)
+            .scoring(|brick| 100 * brick.row + 10 * brick.combo + brick.ball_speed as usize)
+            .build();
+        let brick = single_brick(&mut game);
+        game.bricks.push(Brick::new(Rectf64 {
+            x: 10.,
+            ..brick.clone()
+        }));
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 2.,
+            3.,
+            30.,
+            40.,
+        )];
+
+        game.check_collisions();
+        // The brick's top is at 125, 11 brick heights below the top of the playfield.
+        assert_eq!(game.score, 1100 + 10 + 50);
+
+        // Tougher bricks can be worth more.
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(10)
+            .scoring(|brick| 10 * usize::from(brick.full_hp) + usize::from(brick.hp))
+            .build();
+        let brick = single_brick(&mut game);
+        let far = Ball::new(10., 10., 3., 0., 40.);
+        game.bricks = vec![Brick::new(brick.clone()).with_hp(3)];
+        for _ in 0..2 {
+            game.bricks[0].hit();
+            game.bricks[0].settle(&far);
+        }
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 2.,
+            3.,
+            0.,
+            40.,
+        )];
+        game.check_collisions();
+        assert!(game.bricks.is_empty());
+        assert_eq!(game.score, 30);
+    }
+
+    #[test]
+    fn test_combo_points() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(10)
+            .scoring(combo_points)
+            .death_animation(0.)
+            .build();
+        let brick = single_brick(&mut game);
+        game.bricks.push(Brick::new(Rectf64 {
+            x: brick.x + brick.width,
+            ..brick.clone()
+        }));
+        game.bricks.push(Brick::new(Rectf64 {
+            x: 10.,
+            ..brick.clone()
+        }));
+        // Both bricks broken at once escalate the combo, scoring 1 + 2.
+        game.balls = vec![Ball::new(brick.x + brick.width, brick.y - 2., 3., 0., 40.)];
+        game.check_collisions();
+        assert_eq!(game.combo(), 2);
+        assert_eq!(game.score, 3);
+
+        // Touching the paddle resets the multiplier.
+        let paddle = game.paddle.area();
+        game.balls = vec![Ball::new(
+            paddle.x + paddle.width / 2.,
+            paddle.top() + 2.,
+            3.,
+            0.,
+            -40.,
+        )];
+        game.check_collisions();
+        assert_eq!(game.combo(), 0);
+        game.balls = vec![Ball::new(15., brick.y - 2., 3., 0., 40.)];
+        game.check_collisions();
+        assert_eq!(game.combo(), 1);
+        assert_eq!(game.score, 4);
+    }
+
+    #[test]
+    fn test_from_parts() {
+        let built = game();
+        let paddle = built.paddle.area();
+        let ball = Ball::new(100., 60., 3., 0., -30.);
+        let brick = Rectf64 {
+            x: 100.,
+            y: 100.,
+            width: BRICK_WIDTH,
+            height: BRICK_HEIGHT,
+        };
+        let parts = |balls, brick| {
+            Game::from_parts(
+                built.area.clone(),
+                Paddle::new(paddle.clone(), 2., 358., 8., Color::Red),
+                balls,
+                built.walls.clone(),
+                built.bottom.clone(),
+                vec![Brick::new(brick)],
+            )
+        };
+
+        let game = parts(vec![ball.clone()], brick.clone()).unwrap();
+        assert_eq!(game.state(), GameState::Running);
+        assert_eq!(game.score(), 0);
+        assert_eq!(game.ball_position(), (100., 60.));
+        assert_eq!(game.bricks().len(), 1);
+
+        // Several balls can be in play from the start.
+        let second = Ball::new(200., 80., 3., 30., 30.);
+        let game = parts(vec![ball.clone(), second.clone()], brick.clone()).unwrap();
+        let positions: Vec<_> = game.balls().iter().map(Ball::position).collect();
+        assert_eq!(positions, [(100., 60.), (200., 80.)]);
+
+        assert!(parts(vec![], brick.clone()).is_err());
+        assert!(parts(vec![ball.clone(); MAX_BALLS + 1], brick.clone()).is_err());
+        let outside = Ball::new(400., 60., 3., 0., -30.);
+        assert!(parts(vec![ball.clone(), outside], brick.clone()).is_err());
+        assert!(parts(vec![ball], Rectf64 { y: 178., ..brick }).is_err());
+    }
+
+    #[test]
+    fn test_brick_region() {
+        let region = Rectf64 {
+            x: 100.,
+            y: 30.,
+            width: 100.,
+            height: 40.,
+        };
+        let game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(1000)
+            .brick_region(region.clone())
+            .build();
+        assert!(!game.bricks.is_empty());
+        assert!(game.bricks.len() <= (100. * 40. / (BRICK_WIDTH * BRICK_HEIGHT)) as usize);
+        assert!(game
+            .bricks
+            .iter()
+            .all(|brick| region.contains(&brick.area())));
+
+        // The region is kept clear of the paddle and the ball on it.
+        let game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(1000)
+            .brick_region(Rectf64 {
+                y: 0.,
+                ..region.clone()
+            })
+            .build();
+        let clear = game.paddle.area().top() + 2. * BALL_RADIUS;
+        assert!(game
+            .bricks
+            .iter()
+            .all(|brick| brick.area().bottom() >= clear));
+
+        // A region too small for a single brick leaves none.
+        let game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(1000)
+            .brick_region(Rectf64 {
+                x: 0.,
+                width: 10.,
+                ..region
+            })
+            .build();
+        assert!(game.bricks.is_empty());
+    }
+
+    #[test]
+    fn test_restitution_is_recovered_by_the_paddle() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(30.)
+            .brick_count(10)
+            .restitution(0.5)
+            // Keep the paddle's spin out of the ball's speed.
+            .paddle_bounce_angle(paddle::MAX_BOUNCE_ANGLE)
+            .build();
+        let launch = game.ball_speed();
+
+        // Off a wall the ball loses speed.
+        let mut ball = game.spawn.clone();
+        ball.place(360. - WALL_W - 2., 90.);
+        game.balls = vec![ball];
+        game.check_collisions();
+        assert!(game.ball_speed() < launch);
+
+        // And the paddle gives it back, bouncing the ball only once.
+        let paddle = game.paddle.area();
+        let mut ball = game.balls[0].clone();
+        ball.rebound((0., -1.));
+        ball.place(paddle.x + paddle.width / 2., paddle.top() + 2.);
+        game.balls = vec![ball];
+        for _ in 0..10 {
+            game.event(GameEvent::Tick { dt: 1. / 120. });
+        }
+        assert_eq!(game.paddle_hits(), 1);
+        assert!(game.balls[0].velocity().1 > 0.);
+        assert!((game.ball_speed() - launch).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_restitution_is_capped() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(30.)
+            .brick_count(10)
+            .restitution(2.)
+            .max_ball_speed(60.)
+            .build();
+        let launch = game.ball_speed();
+        let mut ball = game.spawn.clone();
+        ball.place(360. - WALL_W - 2., 90.);
+        game.balls = vec![ball];
+        game.check_collisions();
+        assert!(game.ball_speed() > launch);
+
+        // However many times it bounces, the ball doesn't get faster than the top speed.
+        for _ in 0..10 {
+            // Put the ball next to the wall it's heading for.
+            let mut ball = game.balls[0].clone();
+            let x = if ball.velocity().0 > 0. {
+                360. - WALL_W - 2.
+            } else {
+                WALL_W + 2.
+            };
+            ball.place(x, 90.);
+            game.balls = vec![ball];
+            game.check_collisions();
+        }
+        assert!((game.ball_speed() - 60.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_speed_per_brick() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(10)
+            .speed_per_brick(5.)
+            .max_ball_speed(62.)
+            .build();
+        let brick = single_brick(&mut game);
+        let mut speed = 50.;
+        for expected in [55., 60., 62., 62.] {
+            // Hit the brick from below, with the speed gained so far.
+            game.bricks = vec![Brick::new(brick.clone())];
+            game.balls = vec![Ball::new(
+                brick.x + brick.width / 2.,
+                brick.y - 2.,
+                3.,
+                0.,
+                speed,
+            )];
+            game.check_collisions();
+            let (vx, vy) = game.balls[0].velocity();
+            speed = vx.hypot(vy);
+            assert!((speed - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_random_brick_colors() {
+        let colors = |seed| {
+            GameOptions::default()
+                .area(Rect::new(0, 0, 360, 180).into())
+                .generator(3, 1)
+                .seed(seed)
+                .random_brick_colors(true)
+                .build()
+                .bricks
+                .iter()
+                .map(|brick| (brick.area().x, brick.area().y, brick.color()))
+                .collect::<Vec<_>>()
+        };
+        let first = colors(7);
+        assert!(first.iter().all(|(_, _, color)| color.is_some()));
+        assert_eq!(first, colors(7));
+        assert_ne!(first, colors(8));
+
+        let game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .generator(3, 1)
+            .build();
+        assert!(game.bricks.iter().all(|brick| brick.color().is_none()));
+    }
+
+    #[test]
+    fn test_compaction() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(10)
+            .compaction(true)
+            .build();
+        let brick = single_brick(&mut game);
+        let above = |dx: f64, rows: f64| {
+            Brick::new(Rectf64 {
+                x: brick.x + dx,
+                y: brick.y + rows * BRICK_HEIGHT,
+                ..brick.clone()
+            })
+        };
+        game.bricks
+            .extend([above(0., 1.), above(BRICK_WIDTH, 2.), above(-50., -1.)]);
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 2.,
+            3.,
+            0.,
+            40.,
+        )];
+
+        game.check_collisions();
+        let mut ys: Vec<_> = game.bricks.iter().map(|brick| brick.area().y).collect();
+        ys.sort_by(f64::total_cmp);
+        // The brick below the cleared row stays put.
+        assert_eq!(
+            ys,
+            [brick.y - BRICK_HEIGHT, brick.y, brick.y + BRICK_HEIGHT]
+        );
+    }
+
+    #[test]
+    fn test_stalling_ball_is_nudged_down() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_max_airtime(Some(5.))
+            .build();
+        single_brick(&mut game);
+        // The ball bounces between the side walls, above the paddle and below the brick.
+        for (vx, vy) in [(120., 0.), (-120., 0.), (-100., 2.)] {
+            game.balls = vec![Ball::new(180., 90., 3., vx, vy)];
+            for _ in 0..(4.9 * 24.) as usize {
+                game.move_ball(1. / 24.);
+            }
+            assert!(game.balls[0].velocity().1 >= 0.);
+
+            for _ in 0..5 {
+                game.move_ball(1. / 24.);
+            }
+            let (new_vx, new_vy) = game.balls[0].velocity();
+            assert!((new_vy / new_vx.abs() + 1.).abs() < 1e-9);
+            assert!((new_vx.hypot(new_vy) - f64::hypot(vx, vy)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_airtime_is_tracked_per_ball() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_max_airtime(Some(0.5))
+            .build();
+        let brick = single_brick(&mut game);
+        game.bricks = vec![Brick::new(brick.clone()).with_hp(100)];
+        // One ball bounces between the side walls, the other one off the brick above it.
+        game.balls = vec![
+            Ball::new(180., 90., 3., 120., 0.),
+            Ball::new(brick.x + 7., 100., 3., 0., 48.),
+        ];
+        for _ in 0..15 {
+            game.move_ball(1. / 24.);
+        }
+        assert!(game.balls[0].velocity().1 < 0.);
+        assert!(game.balls[1].airtime() > 0.);
+        assert!(game.balls[1].airtime() < 0.5);
+    }
+
+    #[test]
+    fn test_fire_streak() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(10)
+            .fire_streak(2)
+            .build();
+        let brick = single_brick(&mut game);
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 2.,
+            3.,
+            30.,
+            40.,
+        )];
+        game.combo = 1;
+        game.check_collisions();
+        assert!(game.balls[0].is_on_fire());
+
+        // Above the threshold a multi-hit brick dies in one hit.
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(10)
+            .max_brick_hp(3)
+            .fire_streak(2)
+            .build();
+        let brick = single_brick(&mut game);
+        game.bricks = vec![Brick::new(brick.clone()).with_hp(3)];
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 2.,
+            3.,
+            30.,
+            40.,
+        )];
+        game.balls[0].set_on_fire(true);
+        game.combo = 2;
+        game.check_collisions();
+        assert!(game.bricks.is_empty());
+        assert_eq!(game.score, 1);
+
+        // Touching the paddle puts the fire out.
+        let paddle = game.paddle.area();
+        game.balls = vec![Ball::new(
+            paddle.x + paddle.width / 2.,
+            paddle.top() + 2.,
+            3.,
+            0.,
+            -40.,
+        )];
+        game.balls[0].set_on_fire(true);
+        game.check_collisions();
+        assert_eq!(game.combo, 0);
+        assert!(!game.balls[0].is_on_fire());
+    }
+
+    #[test]
+    fn test_fog_reveals_bricks_near_the_ball() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(10)
+            .fog(true)
+            .build();
+        assert!(game.bricks.iter().all(Brick::is_hidden));
+
+        let brick = single_brick(&mut game);
+        game.bricks = vec![Brick::new(brick.clone()).hidden(true)];
+        let below = brick.y - 3. - REVEAL_RADIUS;
+        game.balls = vec![Ball::new(brick.x, below - 1., 3., 0., 24.)];
+        game.event(GameEvent::Tick { dt: 0.01 });
+        assert!(game.bricks[0].is_hidden());
+
+        game.event(GameEvent::Tick { dt: 0.05 });
+        assert!(!game.bricks[0].is_hidden());
+    }
+
+    #[test]
+    fn test_random_paddle_start() {
+        let start = |seed| {
+            let game = GameOptions::default()
+                .area(Rect::new(0, 0, 360, 180).into())
+                .random_paddle_start(true)
+                .seed(seed)
+                .build();
+            (game.paddle.area(), game.balls[0].position())
+        };
+        for seed in 0..20 {
+            let (paddle, ball) = start(seed);
+            assert_eq!(start(seed), (paddle.clone(), ball));
+            assert!(paddle.left() >= WALL_W && paddle.right() <= 360. - WALL_W);
+            assert!((ball.0 - (paddle.x + paddle.width / 2. - 3.)).abs() < 1e-9);
+        }
+        assert_ne!(start(1).0, start(2).0);
+
+        // Walls too thick for the paddle to fit between don't panic.
+        let game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .wall_thickness(178., 2.)
+            .random_paddle_start(true)
+            .build();
+        assert_eq!(game.paddle.area().left(), 178.);
+    }
+
+    #[test]
+    fn test_monochrome_draws_without_colors() {
+        /// Records the colors drawn with, headlessly.
+        #[derive(Default)]
+        struct Colors(Vec<Color>);
+
+        impl GameRenderer for Colors {
+            fn draw_point(&mut self, _x: f64, _y: f64, color: Color) {
+                self.0.push(color);
+            }
+
+            fn draw_line(&mut self, _x1: f64, _y1: f64, _x2: f64, _y2: f64, color: Color) {
+                self.0.push(color);
+            }
+
+            fn draw_circle(&mut self, _x: f64, _y: f64, _radius: f64, color: Color) {
+                self.0.push(color);
+            }
+        }
+
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .walls_color(Color::Blue)
+            .paddle_color(Color::LightGreen)
+            .brick_count(10)
+            .background("level".into())
+            .color_mode(ColorMode::Monochrome)
+            .build();
+        game.force_state(GameState::Won);
+        let mut colors = Colors::default();
+        game.render(&mut colors);
+
+        assert!(!colors.0.is_empty());
+        assert!(colors.0.iter().all(|&color| color == Color::Reset));
+    }
+
+    #[test]
+    fn test_lives_as_ball_icons() {
+        /// Records the radii of the circles and the texts drawn, headlessly.
+        #[derive(Default)]
+        struct Recorder {
+            radii: Vec<f64>,
+            texts: Vec<String>,
+        }
+
+        impl GameRenderer for Recorder {
+            fn draw_point(&mut self, _x: f64, _y: f64, _color: Color) {}
+
+            fn draw_line(&mut self, _x1: f64, _y1: f64, _x2: f64, _y2: f64, _color: Color) {}
+
+            fn draw_circle(&mut self, _x: f64, _y: f64, radius: f64, _color: Color) {
+                self.radii.push(radius);
+            }
+
+            fn draw_text(
+                &mut self,
+                text: &str,
+                _position: (f64, f64),
+                _spacing: f64,
+                _color: Color,
+            ) {
+                self.texts.push(text.to_string());
+            }
+        }
+
+        let options = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(48.)
+            .brick_count(10)
+            .lives(3);
+        let mut recorder = Recorder::default();
+        options
+            .clone()
+            .lives_display(LivesDisplay::Icons)
+            .build()
+            .render(&mut recorder);
+        // One icon for every life, drawn like a smaller ball.
+        let icons = recorder
+            .radii
+            .iter()
+            .filter(|&&radius| radius == LIFE_ICON_RADIUS)
+            .count();
+        assert_eq!(icons, 3);
+        assert!(!recorder.texts.iter().any(|text| text.starts_with("lives")));
+
+        let mut recorder = Recorder::default();
+        options.build().render(&mut recorder);
+        assert!(!recorder.radii.contains(&LIFE_ICON_RADIUS));
+        assert!(recorder.texts.contains(&"lives: 3".to_string()));
+    }
+
+    #[test]
+    fn test_predicted_landing_x() {
+        let mut game = game();
+        game.balls = vec![Ball::new(300., 100., 3., 0., 60.)];
+        assert_eq!(game.predicted_landing_x(), None);
+
+        // Straight down.
+        game.balls = vec![Ball::new(100., 100., 3., 0., -60.)];
+        assert_eq!(game.predicted_landing_x(), Some(100.));
+
+        // The paddle's top is at 5.6, so the ball comes down 91.4 units and would end up at
+        // 391.4 without the right wall. Its center bounces off at 355 and comes back by 36.4.
+        game.balls = vec![Ball::new(300., 100., 3., 60., -60.)];
+        let x = game.predicted_landing_x().unwrap();
+        assert!((x - 318.6).abs() < 1e-9, "{x}");
+
+        // Following the ball confirms the prediction.
+        game.bricks.clear();
+        game.bricks.push(Brick::new(Rectf64 {
+            x: 0.,
+            y: 170.,
+            width: 1.,
+            height: 1.,
+        }));
+        for _ in 0..10_000 {
+            game.balls[0].mov(0.001);
+            game.check_collisions();
+            if game.balls[0].position().1 <= 8.6 {
+                break;
+            }
+        }
+        assert!((game.balls[0].position().0 - x).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_frame_delta_is_clamped() {
+        let mut game = game();
+        let (x, y) = game.balls[0].position();
+        game.event(GameEvent::Tick { dt: 1000. });
+        let (new_x, new_y) = game.balls[0].position();
+        assert!((new_x - x - 48. * MAX_FRAME_DELTA).abs() < 1e-9);
+        assert!((new_y - y - 48. * MAX_FRAME_DELTA).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_edge_catch_bonus() {
+        let mut game = game();
+        game.bricks.clear();
+        game.bricks.push(Brick::new(Rectf64 {
+            x: 0.,
+            y: 170.,
+            width: 1.,
+            height: 1.,
+        }));
+        let paddle = game.paddle.area();
+
+        // A hit with the center of the paddle doesn't earn a bonus.
+        game.balls = vec![Ball::new(
+            paddle.x + paddle.width / 2.,
+            paddle.top() + 1.,
+            3.,
+            0.,
+            -48.,
+        )];
+        game.check_collisions();
+        assert_eq!(game.score, 0);
+        assert!(game.popups.is_empty());
+
+        // A hit with the edge does.
+        game.balls = vec![Ball::new(
+            paddle.right() - 1.,
+            paddle.top() + 1.,
+            3.,
+            0.,
+            -48.,
+        )];
+        game.check_collisions();
+        assert_eq!(game.score, EDGE_BONUS);
+        assert_eq!(game.popups.len(), 1);
+    }
+
+    #[test]
+    fn test_animations_play_while_paused() {
+        let mut game = game();
+        game.popups.push(ScorePopup {
+            text: "+2".into(),
+            position: (100., 100.),
+            ttl: POPUP_TTL,
+        });
+        let position = game.balls[0].position();
+
+        // A paused game gets no ticks, only the animation steps.
+        game.animate(POPUP_TTL / 2.);
+        game.idle(POPUP_TTL / 2.);
+        assert_eq!(game.popups[0].ttl, POPUP_TTL / 2.);
+        assert_eq!(game.pulse_phase, POPUP_TTL / 2.);
+        assert_eq!(game.balls[0].position(), position);
+
+        game.animate(POPUP_TTL);
+        assert!(game.popups.is_empty());
+    }
+
+    #[test]
+    fn test_idle_pulse() {
+        let mut game = game();
+        game.idle(PULSE_PERIOD / 2.);
+        assert_eq!(game.pulse_phase, PULSE_PERIOD / 2.);
+
+        game.event(GameEvent::Tick { dt: 0.01 });
+        assert_eq!(game.pulse_phase, 0.);
+
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(10)
+            .brick_pulse(false)
+            .build();
+        game.idle(PULSE_PERIOD / 2.);
+        assert_eq!(game.pulse_phase, 0.);
+    }
+
+    #[test]
+    fn test_brick_bounce_without_jitter_is_reflective() {
+        let mut game = game();
+        let brick = single_brick(&mut game);
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 2.,
+            3.,
+            20.,
+            30.,
+        )];
+        game.check_collisions();
+        assert_eq!(game.balls[0].velocity(), (20., -30.));
+    }
+
+    #[test]
+    fn test_splitting_brick_leaves_two_halves() {
+        let mut game = game();
+        let brick = single_brick(&mut game);
+        game.bricks = vec![Brick::new(brick.clone()).splitting(1)];
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 2.,
+            3.,
+            20.,
+            30.,
+        )];
+
+        game.check_collisions();
+        assert_eq!(game.bricks.len(), 2);
+        assert_eq!(game.score, 1);
+        assert_eq!(game.state, GameState::Running);
+
+        // The ball still touching the halves doesn't destroy them right away.
+        game.check_collisions();
+        assert_eq!(game.bricks.len(), 2);
+
+        // Once it has left them, they are solid.
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 10.,
+            3.,
+            20.,
+            30.,
+        )];
+        game.check_collisions();
+        game.balls = vec![Ball::new(brick.x + 1., brick.y - 2., 3., 20., 30.)];
+        game.check_collisions();
+        assert_eq!(game.bricks.len(), 1);
+        assert_eq!(game.score, 2);
+    }
+
+    #[cfg(feature = "debug")]
+    #[test]
+    fn test_recent_events() {
+        let mut game = game();
+        let brick = single_brick(&mut game);
+        game.bricks.push(Brick::new(Rectf64 {
+            x: 10.,
+            ..brick.clone()
+        }));
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 2.,
+            3.,
+            20.,
+            30.,
+        )];
+        game.check_collisions();
+        game.balls = vec![Ball::new(1., 90., 3., -20., 30.)];
+        game.check_collisions();
+        assert_eq!(
+            game.recent_events().cloned().collect::<Vec<_>>(),
+            vec![GameEffect::BrickBreak { count: 1 }, GameEffect::WallBounce]
+        );
+
+        for _ in 0..EFFECT_LOG_SIZE {
+            game.balls = vec![Ball::new(1., 90., 3., -20., 30.)];
+            game.check_collisions();
+        }
+        assert_eq!(game.recent_events().count(), EFFECT_LOG_SIZE);
+        assert!(game
+            .recent_events()
+            .all(|effect| *effect == GameEffect::WallBounce));
+    }
+
+    #[test]
+    fn test_bounce_jitter_keeps_speed() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(10)
+            .bounce_jitter(0.2)
+            .build();
+        let brick = single_brick(&mut game);
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 2.,
+            3.,
+            20.,
+            30.,
+        )];
+        game.check_collisions();
+        let (vx, vy) = game.balls[0].velocity();
+        assert!((vx.hypot(vy) - 20f64.hypot(30.)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_death_animation() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(10)
+            .death_animation(0.15)
+            .lives(1)
+            .build();
+        game.balls = vec![Ball::new(180., 1., 3., 0., -48.)];
+        game.check_collisions();
+        assert_eq!(game.state, GameState::Running);
+        assert!(game.dying.is_some());
+
+        // The ball is frozen during the animation.
+        let position = game.balls[0].position();
+        game.event(GameEvent::Tick { dt: 0.1 });
+        assert_eq!(game.state, GameState::Running);
+        assert_eq!(game.balls[0].position(), position);
+
+        game.event(GameEvent::Tick { dt: 0.1 });
+        assert_eq!(game.state, GameState::Lost);
+
+        // Without the animation the game is lost right away.
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(10)
+            .death_animation(0.)
+            .lives(1)
+            .build();
+        game.balls = vec![Ball::new(180., 1., 3., 0., -48.)];
+        game.check_collisions();
+        assert_eq!(game.state, GameState::Lost);
+    }
+
+    #[test]
+    fn test_only_destroyed_bricks_score() {
+        let mut game = game();
+        let brick = single_brick(&mut game);
+        game.bricks = vec![Brick::new(brick.clone()).with_hp(2)];
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 2.,
+            3.,
+            30.,
+            40.,
+        )];
+        game.check_collisions();
+        assert_eq!(game.score(), 0);
+        assert_eq!(game.bricks.len(), 1);
+        assert_eq!(game.bricks[0].hp(), 1);
+
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 10.,
+            3.,
+            30.,
+            40.,
+        )];
+        game.check_collisions();
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 2.,
+            3.,
+            30.,
+            40.,
+        )];
+        game.check_collisions();
+        assert_eq!(game.score(), 1);
+        assert_eq!(game.state, GameState::Won);
+    }
+
+    #[test]
+    fn test_seeded_layout() {
+        let layout = |seed| {
+            GameOptions::default()
+                .area(Rect::new(0, 0, 360, 180).into())
+                .brick_count(20)
+                .seed(seed)
+                .build()
+                .bricks
+        };
+        assert_eq!(layout(42), layout(42));
+        assert_ne!(layout(42), layout(43));
+    }
+
+    #[test]
+    fn test_brick_size_and_wall_thickness() {
+        let game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(1000)
+            .brick_size(20., 10.)
+            .wall_thickness(6., 4.)
+            .build();
+        // The brick count is clamped to what fits with the configured sizes.
+        assert!(game.bricks.len() <= (348. * 86. / (20. * 10.)) as usize);
+        assert!(game.bricks.iter().all(|brick| {
+            let area = brick.area();
+            area.width == 20.
+                && area.height == 10.
+                && area.left() >= 6.
+                && area.right() <= 354.
+                && area.top() <= 176.
+        }));
+        assert_eq!(game.walls.left.area().width, 6.);
+        assert_eq!(game.walls.top.area().height, 4.);
+        assert_eq!(game.paddle.area().bottom(), 4.);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_save_and_load() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(48.)
+            .brick_count(30)
+            .max_brick_hp(3)
+            .lives(2)
+            .build();
+        for _ in 0..50 {
+            game.event(GameEvent::Tick { dt: 0.05 });
+        }
+        let path = std::env::temp_dir().join(format!("arkanoid-save-{}.json", std::process::id()));
+        game.save(&path).unwrap();
+        let mut resumed = Game::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(resumed.bricks, game.bricks);
+        assert_eq!(resumed.score(), game.score());
+        assert_eq!(resumed.lives(), game.lives());
+        assert_eq!(resumed.paddle.area(), game.paddle.area());
+
+        // The resumed game plays on just like the saved one.
+        for _ in 0..50 {
+            game.event(GameEvent::Tick { dt: 0.05 });
+            resumed.event(GameEvent::Tick { dt: 0.05 });
+        }
+        assert_eq!(resumed.ball_position(), game.ball_position());
+        assert_eq!(resumed.bricks, game.bricks);
+
+        assert!(Game::load(Path::new("/nonexistent/save.json")).is_err());
+    }
+
+    #[test]
+    fn test_set_scoring() {
+        let mut game = game();
+        game.set_scoring(|_| 5);
+        let brick = single_brick(&mut game);
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 2.,
+            3.,
+            0.,
+            40.,
+        )];
+        game.check_collisions();
+        assert_eq!(game.score(), 5);
+        assert_eq!(
+            (game.level_options.scoring)(&BrickBreak {
+                row: 0,
+                combo: 1,
+                ball_speed: 0.,
+                hp: 0,
+                full_hp: 1,
+            }),
+            5
+        );
+    }
+
+    #[test]
+    fn test_level_file() {
+        let path = std::env::temp_dir().join(format!("arkanoid-level-{}.txt", std::process::id()));
+        std::fs::write(&path, "##3\n.#.\n").unwrap();
+        let game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(50)
+            .max_brick_hp(2)
+            .level_file(path.clone())
+            .build();
+        std::fs::remove_file(&path).unwrap();
+        let hps: Vec<_> = game.bricks.iter().map(Brick::hp).collect();
+        assert_eq!(hps, [1, 1, 3, 1]);
+
+        // A missing file falls back to the random layout.
+        let game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(50)
+            .level_file(path.clone())
+            .build();
+        assert_eq!(game.bricks.len(), 50);
+
+        // A layout read beforehand takes precedence.
+        let game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(50)
+            .level_file(path)
+            .level_layout("#.#\n".into())
+            .build();
+        assert_eq!(game.bricks.len(), 2);
+
+        // The fallback can be told apart from a level loading fine.
+        let options = GameOptions::default().area(Rect::new(0, 0, 360, 180).into());
+        assert!(options.check_level().is_ok());
+        assert!(options
+            .clone()
+            .level_layout("#".into())
+            .check_level()
+            .is_ok());
+        assert!(options.level_layout("#x".into()).check_level().is_err());
+    }
+
+    #[test]
+    fn test_level_file_gaps() {
+        let path = std::env::temp_dir().join(format!("arkanoid-gaps-{}.txt", std::process::id()));
+        std::fs::write(&path, "gaps: 170:190\n#\n").unwrap();
+        let escape = |path: Option<&std::path::Path>| {
+            let mut options = GameOptions::default()
+                .area(Rect::new(0, 0, 360, 180).into())
+                .ball_speed(48.)
+                .death_animation(0.)
+                .lives(3);
+            if let Some(path) = path {
+                options = options.level_file(path.to_path_buf());
+            }
+            let mut game = options.build();
+            game.bricks.retain(|brick| brick.area().x < 100.);
+            game.balls = vec![Ball::new(180., 170., 3., 0., 40.)];
+            for _ in 0..20 {
+                game.move_ball(1. / 24.);
+            }
+            game.lives()
+        };
+        // The ball lined up with the gap escapes through it, it bounces off the wall otherwise.
+        assert_eq!(escape(Some(&path)), 2);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(escape(None), 3);
+    }
+
+    #[test]
+    fn test_max_brick_hp() {
+        let game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(50)
+            .max_brick_hp(3)
+            .seed(7)
+            .build();
+        assert!(game
+            .bricks
+            .iter()
+            .all(|brick| (1..=3).contains(&brick.hp())));
+        assert!(game.bricks.iter().any(|brick| brick.hp() > 1));
+    }
+
+    #[test]
+    fn test_sticky_launch() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(48.)
+            .brick_count(10)
+            .sticky_launch(true)
+            .death_animation(0.)
+            .build();
+        let start = game.balls[0].position();
+        game.event(GameEvent::Tick { dt: 0.1 });
+        assert_eq!(game.balls[0].position(), start);
+
+        // The ball moves along with the paddle, and is launched the way the paddle moved.
+        game.event(GameEvent::MovePad {
+            direction: Direction::Left,
+        });
+        let (x, y) = game.balls[0].position();
+        assert_eq!(x, game.paddle.center() - BALL_RADIUS);
+        assert_eq!(y, start.1);
+        // The aim line follows the way the ball will be launched.
+        let aim = game.aim_line();
+        assert_eq!(aim.len(), AIM_LINE_DOTS);
+        assert!(aim[0].0 < x && aim[0].1 > y);
+        game.event(GameEvent::Launch);
+        assert_eq!(game.balls[0].velocity(), (-48., 48.));
+        assert!(game.aim_line().is_empty());
+        game.event(GameEvent::Tick { dt: 0.1 });
+        assert_ne!(game.balls[0].position(), (x, y));
+
+        // A lost ball rests on the paddle again, launched the way it was at the start.
+        game.balls = vec![Ball::new(20., 1., 3., 0., -30.)];
+        game.check_collisions();
+        assert!(game.ball_attached);
+        game.event(GameEvent::Launch);
+        assert_eq!(game.balls[0].velocity(), (48., 48.));
+    }
+
+    #[test]
+    fn test_powerups() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(48.)
+            .brick_count(10)
+            .powerup_chance(1.)
+            .build();
+        let brick = single_brick(&mut game);
+        game.bricks.push(Brick::new(Rectf64 {
+            x: 10.,
+            ..brick.clone()
+        }));
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 2.,
+            3.,
+            30.,
+            40.,
+        )];
+        game.check_collisions();
+        assert_eq!(game.powerups.len(), 1);
+
+        // A power-up falling onto the paddle is caught.
+        let lives = game.lives();
+        let top = game.paddle.area().top();
+        game.powerups = vec![PowerUp::new(
+            PowerUpKind::ExtraLife,
+            (game.paddle.center(), top + 4.),
+        )];
+        game.drop_powerups(0.1);
+        assert!(game.powerups.is_empty());
+        assert_eq!(game.lives(), lives + 1);
+
+        let speed = 30f64.hypot(40.);
+        game.apply_powerup(PowerUpKind::SlowBall);
+        let (vx, vy) = game.balls[0].velocity();
+        assert!((vx.hypot(vy) - speed * SLOW_BALL).abs() < 1e-9);
+
+        // And a missed one is gone once it reaches the bottom.
+        game.powerups = vec![PowerUp::new(PowerUpKind::ExtraLife, (10., 8.))];
+        game.drop_powerups(0.1);
+        assert_eq!(game.powerups.len(), 1);
+        game.drop_powerups(1.);
+        assert!(game.powerups.is_empty());
+        assert_eq!(game.lives(), lives + 1);
+    }
+
+    #[test]
+    fn test_wall_bounce_splits_the_ball() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(48.)
+            .brick_count(10)
+            .wall_split_chance(1.)
+            .build();
+        game.balls = vec![Ball::new(4., 90., 3., -30., 40.)];
+        game.check_collisions();
+        assert_eq!(game.balls().len(), 2);
+        for ball in game.balls() {
+            let (vx, vy) = ball.velocity();
+            assert!(vx > 0.);
+            assert!((vx.hypot(vy) - 50.).abs() < 1e-9);
+        }
+
+        // The halves only split again once they bounce off a wall again.
+        game.check_collisions();
+        assert_eq!(game.balls().len(), 2);
+    }
+
+    #[test]
+    fn test_balls_bounce_off_each_other() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(48.)
+            .brick_count(10)
+            .ball_collisions(true)
+            .build();
+        game.balls = vec![
+            Ball::new(100., 60., 3., 30., 10.),
+            Ball::new(110., 60., 3., -30., 10.),
+        ];
+        for _ in 0..4 {
+            game.move_ball(1. / 24.);
+        }
+        // The balls swap their horizontal velocities, the vertical ones are left alone.
+        assert_eq!(game.balls[0].velocity(), (-30., 10.));
+        assert_eq!(game.balls[1].velocity(), (30., 10.));
+        let (x0, _) = game.balls[0].position();
+        let (x1, _) = game.balls[1].position();
+        assert!(x1 - x0 > 6.);
+    }
+
+    #[test]
+    fn test_multi_ball() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(48.)
+            .brick_count(10)
+            .death_animation(0.)
+            .lives(2)
+            .build();
+        game.balls = vec![Ball::new(100., 100., 3., 30., 40.)];
+        game.apply_powerup(PowerUpKind::MultiBall);
+        let velocities: Vec<_> = game.balls().iter().map(Ball::velocity).collect();
+        assert_eq!(velocities, [(30., 40.), (-30., 40.)]);
+
+        // A vertical ball gets a clone flying off at an angle instead of on top of it.
+        game.balls = vec![Ball::new(100., 100., 3., 0., 50.)];
+        game.apply_powerup(PowerUpKind::MultiBall);
+        assert_eq!(game.balls[0].velocity(), (0., 50.));
+        let (vx, vy) = game.balls[1].velocity();
+        assert!((vx + 50. * SPLIT_ANGLE.sin()).abs() < 1e-9);
+        assert!((vx.hypot(vy) - 50.).abs() < 1e-9);
+        game.balls = vec![
+            Ball::new(100., 100., 3., 30., 40.),
+            Ball::new(100., 100., 3., -30., 40.),
+        ];
+
+        // Losing one of the balls doesn't cost a life while another one is in play.
+        game.balls[0] = Ball::new(20., 1., 3., 0., -30.);
+        game.check_collisions();
+        assert_eq!(game.balls().len(), 1);
+        assert_eq!(game.balls[0].velocity(), (-30., 40.));
+        assert_eq!(game.lives(), 2);
+
+        // Only losing the last one does.
+        game.balls[0] = Ball::new(20., 1., 3., 0., -30.);
+        game.check_collisions();
+        assert_eq!(game.balls().len(), 1);
+        assert_eq!(game.lives(), 1);
+    }
+
+    #[test]
+    fn test_spawning_past_the_cap_is_a_noop() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(48.)
+            .brick_count(10)
+            .max_balls(3)
+            .build();
+        game.balls = vec![Ball::new(100., 100., 3., 30., 40.)];
+        assert!(game.spawn_ball(Ball::new(120., 100., 3., -30., 40.)));
+
+        // The split only fills the balls up to the cap.
+        game.apply_powerup(PowerUpKind::MultiBall);
+        assert_eq!(game.balls().len(), 3);
+        game.apply_powerup(PowerUpKind::MultiBall);
+        assert_eq!(game.balls().len(), 3);
+        assert!(!game.spawn_ball(Ball::new(140., 100., 3., 30., 40.)));
+        assert_eq!(game.balls().len(), 3);
+    }
+
+    #[test]
+    fn test_paddle_resize_wears_off() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(48.)
+            .brick_count(10)
+            .powerup_duration(1.)
+            .build();
+        let width = game.paddle.area().width;
+        let center = game.paddle.center();
+        game.balls = vec![Ball::new(100., 100., 3., 30., 40.)];
+        game.apply_powerup(PowerUpKind::WidePaddle);
+        assert_eq!(game.paddle.area().width, width * WIDE_PADDLE);
+        assert_eq!(game.paddle.center(), center);
+        // A later resize replaces the earlier one, and the ball in the air isn't moved.
+        game.apply_powerup(PowerUpKind::NarrowPaddle);
+        assert_eq!(game.paddle.area().width, width * NARROW_PADDLE);
+        assert_eq!(game.balls[0].position(), (100., 100.));
+
+        for _ in 0..5 {
+            game.event(GameEvent::Tick { dt: 0.1 });
+        }
+        assert_eq!(game.paddle.area().width, width * NARROW_PADDLE);
+        for _ in 0..6 {
+            game.event(GameEvent::Tick { dt: 0.1 });
+        }
+        assert_eq!(game.paddle.area().width, width);
+    }
+
+    #[test]
+    fn test_levels() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(48.)
+            .brick_count(10)
+            .level_count(2)
+            .build();
+        game.score = 7;
+        let brick = single_brick(&mut game);
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 2.,
+            3.,
+            30.,
+            40.,
+        )];
+        game.check_collisions();
+        // The next level has more bricks and a faster ball, and the score is kept.
+        assert_eq!(game.state, GameState::Running);
+        assert_eq!(game.level(), 2);
+        assert_eq!(game.bricks.len(), 20);
+        assert_eq!(game.score, 8);
+        let (vx, vy) = game.balls[0].velocity();
+        assert!((vx.hypot(vy) - 48. * LEVEL_SPEEDUP * 2f64.sqrt()).abs() < 1e-9);
+        assert_eq!(game.banner, LEVEL_BANNER);
+        game.animate(LEVEL_BANNER);
+        assert_eq!(game.banner, 0.);
+
+        let brick = single_brick(&mut game);
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 2.,
+            3.,
+            30.,
+            40.,
+        )];
+        game.check_collisions();
+        assert_eq!(game.state, GameState::Won);
+    }
+
+    #[test]
+    fn test_mercy_widens_the_paddle() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(48.)
+            .brick_count(10)
+            .death_animation(0.)
+            .lives(9)
+            .level_count(2)
+            .mercy(true)
+            .build();
+        let base = game.paddle.area().width;
+        for _ in 0..2 {
+            game.balls = vec![Ball::new(20., 1., 3., 0., -30.)];
+            game.check_collisions();
+        }
+        assert_eq!(game.lives(), 7);
+        let width = game.paddle.area().width;
+        assert!((width - base * (1. + 2. * MERCY_WIDENING)).abs() < 1e-9);
+
+        // The widening is capped.
+        for _ in 0..5 {
+            game.balls = vec![Ball::new(20., 1., 3., 0., -30.)];
+            game.check_collisions();
+        }
+        assert!((game.paddle.area().width - base * MERCY_MAX_WIDTH).abs() < 1e-9);
+
+        // And the paddle shrinks back once the level is cleared.
+        let brick = single_brick(&mut game);
+        game.balls = vec![Ball::new(
+            brick.x + brick.width / 2.,
+            brick.y - 2.,
+            3.,
+            30.,
+            40.,
+        )];
+        game.check_collisions();
+        assert_eq!(game.level, 2);
+        assert_eq!(game.paddle.area().width, base);
+    }
+
+    #[test]
+    fn test_lost_ball_takes_a_life() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(48.)
+            .brick_count(10)
+            .death_animation(0.)
+            .lives(2)
+            .build();
+        let launch = game.balls[0].velocity();
+        for _ in 0..5 {
+            game.event(GameEvent::MovePad {
+                direction: Direction::Right,
+            });
+        }
+        game.balls = vec![Ball::new(20., 1., 3., 0., -30.)];
+        game.check_collisions();
+        assert_eq!(game.state, GameState::Running);
+        assert_eq!(game.lives(), 1);
+        // The ball is relaunched from the paddle, wherever it is now.
+        let (x, y) = game.balls[0].position();
+        assert_eq!(x, game.paddle.center() - BALL_RADIUS);
+        assert_eq!(y, game.paddle.collision_area().top() + BALL_RADIUS);
+        assert_eq!(game.balls[0].velocity(), launch);
+
+        game.balls = vec![Ball::new(20., 1., 3., 0., -30.)];
+        game.check_collisions();
+        assert_eq!(game.state, GameState::Lost);
+        assert_eq!(game.lives(), 0);
+    }
+
+    #[test]
+    fn test_added_bricks_delay_the_win() {
+        let mut game = game();
+        game.clear_bricks();
+        let brick = Brick::new(Rectf64 {
+            x: 10.,
+            y: 150.,
+            width: BRICK_WIDTH,
+            height: BRICK_HEIGHT,
+        });
+        game.add_brick(brick).unwrap();
+        game.event(GameEvent::Tick { dt: 0.01 });
+        assert_eq!(game.state, GameState::Running);
+
+        game.clear_bricks();
+        game.event(GameEvent::Tick { dt: 0.01 });
+        assert_eq!(game.state, GameState::Won);
+    }
+
+    #[test]
+    fn test_add_brick_validation() {
+        let mut game = game();
+        let outside = Brick::new(Rectf64 {
+            x: 400.,
+            y: 150.,
+            width: BRICK_WIDTH,
+            height: BRICK_HEIGHT,
+        });
+        assert!(game.add_brick(outside).is_err());
+        let on_paddle = Brick::new(game.paddle.area());
+        assert!(game.add_brick(on_paddle).is_err());
+    }
+
+    #[test]
+    fn test_invasion_reaching_the_paddle_is_lost() {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .brick_count(10)
+            .invasion_mode(true)
+            .build();
+        let rows = game.invasion.as_ref().unwrap().spawn_row.len();
+        assert_eq!(rows, 25);
+
+        // A row descends, but the bricks are still far from the paddle.
+        game.invade(INVASION_INTERVAL);
+        assert_eq!(game.state, GameState::Running);
+
+        // A brick right above the paddle reaches it once the row descends.
+        let paddle = game.paddle.area();
+        game.bricks.push(Brick::new(Rectf64 {
+            x: 10.,
+            y: paddle.top() + 1.,
+            width: BRICK_WIDTH,
+            height: BRICK_HEIGHT,
+        }));
+        game.invade(INVASION_INTERVAL);
+        assert_eq!(game.state, GameState::Lost);
     }
 }
@@ -0,0 +1,388 @@
+use anyhow::Context;
+use crossterm::event::KeyCode;
+use serde::{Deserialize, Deserializer};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// The default time, in milliseconds, repeats of the same action are ignored for.
+pub const DEBOUNCE_MS: u64 = 150;
+
+/// A key action that toggles or switches something, so that holding its key down mustn't
+/// repeat it. Moving the paddle isn't one of them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    /// Pausing and resuming the game, or dismissing the high scores.
+    Pause,
+    /// Asking to quit and confirming it.
+    Quit,
+    /// Restarting the game.
+    Restart,
+}
+
+/// Ignores an action repeated too soon after it was last accepted, e.g. by a held key.
+#[derive(Debug)]
+pub struct Debouncer {
+    /// The time repeats of an action are ignored for.
+    window: Duration,
+    /// When each action was last accepted.
+    last: Vec<(Action, Instant)>,
+}
+
+impl Debouncer {
+    /// Creates a new `Debouncer`.
+    ///
+    /// # Parameters
+    /// - `window`: The time repeats of an action are ignored for, zero to accept them all.
+    ///
+    /// # Returns
+    /// A new `Debouncer` that accepts any action first.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            last: vec![],
+        }
+    }
+
+    /// Checks whether an action should be carried out.
+    ///
+    /// # Parameters
+    /// - `action`: The action of the pressed key.
+    /// - `now`: When the key was pressed.
+    ///
+    /// # Returns
+    /// `false` if the same action was accepted less than the debounce window ago, `true`
+    /// otherwise.
+    pub fn accept(&mut self, action: Action, now: Instant) -> bool {
+        match self.last.iter_mut().find(|(a, _)| *a == action) {
+            Some((_, last)) if now.saturating_duration_since(*last) < self.window => false,
+            Some((_, last)) => {
+                *last = now;
+                true
+            }
+            None => {
+                self.last.push((action, now));
+                true
+            }
+        }
+    }
+}
+
+/// Something a player does with a key that can be bound to another key.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    /// Moving the paddle to the left.
+    MoveLeft,
+    /// Moving the paddle to the right.
+    MoveRight,
+    /// Launching the ball resting on the paddle.
+    Launch,
+    /// Pausing and resuming the game, or dismissing the high scores.
+    Pause,
+    /// Restarting the game.
+    Restart,
+    /// Asking to quit and confirming it.
+    Quit,
+    /// Confirming to quit.
+    Confirm,
+    /// Saving the current frame as ANSI-colored text.
+    Export,
+    /// Saving the game.
+    Save,
+    /// Switching the marker the game is drawn with.
+    Marker,
+}
+
+impl Command {
+    /// Returns what the command does, as shown in the errors about the key bindings.
+    fn description(self) -> &'static str {
+        match self {
+            Self::MoveLeft => "moving the paddle to the left",
+            Self::MoveRight => "moving the paddle to the right",
+            Self::Launch => "launching the ball",
+            Self::Pause => "pausing the game",
+            Self::Restart => "restarting the game",
+            Self::Quit => "quitting the game",
+            Self::Confirm => "confirming to quit",
+            Self::Export => "saving the frame",
+            Self::Save => "saving the game",
+            Self::Marker => "switching markers",
+        }
+    }
+}
+
+/// The keys the player's commands are bound to.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Keymap {
+    /// The key moving the paddle to the left.
+    #[serde(deserialize_with = "deserialize_key")]
+    pub left: KeyCode,
+    /// The key moving the paddle to the right.
+    #[serde(deserialize_with = "deserialize_key")]
+    pub right: KeyCode,
+    /// The key launching the ball.
+    #[serde(deserialize_with = "deserialize_key")]
+    pub launch: KeyCode,
+    /// The key pausing the game.
+    #[serde(deserialize_with = "deserialize_key")]
+    pub pause: KeyCode,
+    /// The key restarting the game.
+    #[serde(deserialize_with = "deserialize_key")]
+    pub restart: KeyCode,
+    /// The key quitting the game.
+    #[serde(deserialize_with = "deserialize_key")]
+    pub quit: KeyCode,
+    /// The key confirming to quit, besides the quit key itself.
+    #[serde(deserialize_with = "deserialize_key")]
+    pub confirm: KeyCode,
+    /// The key saving the current frame.
+    #[serde(deserialize_with = "deserialize_key")]
+    pub export: KeyCode,
+    /// The key saving the game.
+    #[serde(deserialize_with = "deserialize_key")]
+    pub save: KeyCode,
+    /// The key switching markers.
+    #[serde(deserialize_with = "deserialize_key")]
+    pub marker: KeyCode,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            left: KeyCode::Left,
+            right: KeyCode::Right,
+            launch: KeyCode::Char(' '),
+            pause: KeyCode::Enter,
+            restart: KeyCode::Tab,
+            quit: KeyCode::Char('q'),
+            confirm: KeyCode::Char('y'),
+            export: KeyCode::Char('e'),
+            save: KeyCode::Char('s'),
+            marker: KeyCode::Char('m'),
+        }
+    }
+}
+
+impl Keymap {
+    /// Reads the key bindings from a TOML file, e.g. `left = "a"`. The commands missing from
+    /// the file keep their default keys.
+    ///
+    /// # Parameters
+    /// - `path`: The file to read.
+    ///
+    /// # Returns
+    /// The key bindings, or an error if the file can't be read, is malformed or binds a key
+    /// to more than one command.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read the keymap {}", path.display()))?;
+        let keymap: Self = toml::from_str(&text)
+            .with_context(|| format!("malformed keymap {}", path.display()))?;
+        keymap
+            .check()
+            .with_context(|| format!("conflicting keymap {}", path.display()))?;
+        Ok(keymap)
+    }
+
+    /// Checks that no key is bound to more than one command, e.g. after rebinding some.
+    ///
+    /// # Returns
+    /// An error naming the first key bound twice, `Ok` otherwise.
+    pub fn check(&self) -> anyhow::Result<()> {
+        let bindings = self.bindings();
+        for (i, (key, command)) in bindings.iter().enumerate() {
+            if let Some((_, other)) = bindings[..i].iter().find(|(other, _)| other == key) {
+                anyhow::bail!(
+                    "{} is bound to both {} and {}",
+                    key_name(*key),
+                    other.description(),
+                    command.description()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the controls help listing the keys, e.g. shown below the game.
+    pub fn help(&self) -> String {
+        format!(
+            "Use {} {} to move, {} to launch, {} to restart, {} to pause, {} to save the frame, \
+             {} to save the game, {} to switch markers.",
+            key_name(self.left),
+            key_name(self.right),
+            key_name(self.launch),
+            key_name(self.restart),
+            key_name(self.pause),
+            key_name(self.export),
+            key_name(self.save),
+            key_name(self.marker),
+        )
+    }
+
+    /// Returns the command a key is bound to.
+    ///
+    /// # Parameters
+    /// - `code`: The pressed key.
+    ///
+    /// # Returns
+    /// The command bound to the key, or `None` if it isn't bound to any.
+    pub fn command(&self, code: KeyCode) -> Option<Command> {
+        self.bindings()
+            .into_iter()
+            .find(|(key, _)| *key == code)
+            .map(|(_, command)| command)
+    }
+
+    /// Returns the keys along with the commands they're bound to.
+    fn bindings(&self) -> [(KeyCode, Command); 10] {
+        [
+            (self.left, Command::MoveLeft),
+            (self.right, Command::MoveRight),
+            (self.launch, Command::Launch),
+            (self.pause, Command::Pause),
+            (self.restart, Command::Restart),
+            (self.quit, Command::Quit),
+            (self.confirm, Command::Confirm),
+            (self.export, Command::Export),
+            (self.save, Command::Save),
+            (self.marker, Command::Marker),
+        ]
+    }
+}
+
+/// Parses a key, either a single character or the name of a special key, e.g. `left`, `tab`
+/// or `space`. Names are case-insensitive.
+///
+/// # Parameters
+/// - `s`: The key to parse.
+///
+/// # Returns
+/// The key, or an error if it's neither.
+pub fn parse_key(s: &str) -> Result<KeyCode, String> {
+    let mut chars = s.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(KeyCode::Char(c));
+    }
+    Ok(match s.to_lowercase().as_str() {
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "space" => KeyCode::Char(' '),
+        "enter" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "esc" => KeyCode::Esc,
+        _ => return Err(format!("unknown key `{s}`")),
+    })
+}
+
+/// Returns the name of a key, as shown in the controls help.
+pub fn key_name(code: KeyCode) -> String {
+    match code {
+        KeyCode::Left => "←".into(),
+        KeyCode::Right => "→".into(),
+        KeyCode::Up => "↑".into(),
+        KeyCode::Down => "↓".into(),
+        KeyCode::Char(' ') => "SPACE".into(),
+        KeyCode::Enter => "↵".into(),
+        KeyCode::Tab => "TAB".into(),
+        KeyCode::Char(c) => c.to_uppercase().collect(),
+        code => format!("{code:?}").to_uppercase(),
+    }
+}
+
+/// Deserializes a key with [`parse_key`].
+fn deserialize_key<'de, D: Deserializer<'de>>(deserializer: D) -> Result<KeyCode, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    parse_key(&s).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeats_within_the_window_are_ignored() {
+        let mut debouncer = Debouncer::new(Duration::from_millis(DEBOUNCE_MS));
+        let start = Instant::now();
+        let mut pause = false;
+        for ms in [0, 40, 100] {
+            if debouncer.accept(Action::Pause, start + Duration::from_millis(ms)) {
+                pause = !pause;
+            }
+        }
+        assert!(pause);
+
+        // Other actions are debounced separately.
+        assert!(debouncer.accept(Action::Quit, start + Duration::from_millis(100)));
+        // And the window restarts from the last accepted press.
+        assert!(!debouncer.accept(Action::Pause, start + Duration::from_millis(140)));
+        assert!(debouncer.accept(Action::Pause, start + Duration::from_millis(150)));
+    }
+
+    #[test]
+    fn test_zero_window_accepts_everything() {
+        let mut debouncer = Debouncer::new(Duration::ZERO);
+        let now = Instant::now();
+        assert!(debouncer.accept(Action::Restart, now));
+        assert!(debouncer.accept(Action::Restart, now));
+    }
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(parse_key("a"), Ok(KeyCode::Char('a')));
+        assert_eq!(parse_key("Left"), Ok(KeyCode::Left));
+        assert_eq!(parse_key("space"), Ok(KeyCode::Char(' ')));
+        assert!(parse_key("hyper").is_err());
+        assert_eq!(key_name(KeyCode::Char('q')), "Q");
+        assert_eq!(key_name(KeyCode::Tab), "TAB");
+    }
+
+    #[test]
+    fn test_keymap() {
+        let keymap: Keymap = toml::from_str("left = \"a\"\nright = \"d\"").unwrap();
+        assert_eq!(keymap.command(KeyCode::Char('a')), Some(Command::MoveLeft));
+        assert_eq!(keymap.command(KeyCode::Char('d')), Some(Command::MoveRight));
+        assert_eq!(keymap.command(KeyCode::Left), None);
+        // The other commands keep their default keys.
+        assert_eq!(keymap.command(KeyCode::Tab), Some(Command::Restart));
+        assert_eq!(keymap.quit, Keymap::default().quit);
+
+        assert!(toml::from_str::<Keymap>("left = \"hyper\"").is_err());
+        assert!(toml::from_str::<Keymap>("jump = \"w\"").is_err());
+    }
+
+    #[test]
+    fn test_conflicting_keymap() {
+        assert!(Keymap::default().check().is_ok());
+        // The save key defaults to `s`, so moving left with it conflicts.
+        let keymap: Keymap = toml::from_str("left = \"s\"").unwrap();
+        let error = keymap.check().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "S is bound to both moving the paddle to the left and saving the game"
+        );
+        let keymap: Keymap = toml::from_str("left = \"s\"\nsave = \"w\"").unwrap();
+        assert!(keymap.check().is_ok());
+        assert_eq!(keymap.command(KeyCode::Char('w')), Some(Command::Save));
+
+        let path =
+            std::env::temp_dir().join(format!("arkanoid-keymap-{}.toml", std::process::id()));
+        std::fs::write(&path, "left = \"q\"").unwrap();
+        let loaded = Keymap::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(loaded.is_err());
+    }
+
+    #[test]
+    fn test_help() {
+        let keymap: Keymap =
+            toml::from_str("export = \"x\"\nmarker = \"tab\"\nrestart = \"r\"").unwrap();
+        assert_eq!(
+            keymap.help(),
+            "Use ← → to move, SPACE to launch, R to restart, ↵ to pause, X to save the frame, \
+             S to save the game, TAB to switch markers."
+        );
+    }
+}
@@ -1,10 +1,11 @@
-/// Took this implementation from https://github.com/kriskw1999/ratatui-snake
-/// and extended it with the digits.
+//! Took this implementation from https://github.com/kriskw1999/ratatui-snake
+//! and extended it with the digits.
 
-use ratatui::{
-    style::Color,
-    widgets::canvas::{Line, Painter, Shape},
-};
+use crate::color::ColorMode;
+use crate::render::GameRenderer;
+use ratatui::style::Color;
+#[cfg(feature = "tui")]
+use ratatui::widgets::canvas::{Painter, Shape};
 
 pub struct Letter {
     pub lines: Vec<Vec<f64>>,
@@ -13,21 +14,28 @@ pub struct Letter {
     pub color: Color,
 }
 
-impl Shape for Letter {
-    fn draw(&self, painter: &mut Painter) {
+impl Letter {
+    /// Draws the letter's strokes with the given renderer.
+    pub fn render<R: GameRenderer + ?Sized>(&self, renderer: &mut R) {
         for line_points in &self.lines {
-            let line = Line::new(
+            renderer.draw_line(
                 line_points[0] * (self.scale as f64) + self.starting_point.0,
                 line_points[1] * (self.scale as f64) + self.starting_point.1,
                 line_points[2] * (self.scale as f64) + self.starting_point.0,
                 line_points[3] * (self.scale as f64) + self.starting_point.1,
                 self.color,
             );
-            line.draw(painter);
         }
     }
 }
 
+#[cfg(feature = "tui")]
+impl Shape for Letter {
+    fn draw(&self, painter: &mut Painter) {
+        self.render(painter);
+    }
+}
+
 impl Letter {
     pub fn new_letter(letter: char, starting_point: (f64, f64), color: Color) -> Self {
         let lines = match letter {
@@ -279,6 +287,8 @@ impl Letter {
             ],
             // :
             ':' => vec![vec![5.0, 0.5, 5.0, 1.0], vec![5.0, 4.5, 5.0, 5.0]],
+            // +
+            '+' => vec![vec![2.0, 1.0, 2.0, 4.0], vec![0.5, 2.5, 3.5, 2.5]],
             // Space
             ' ' => vec![],
 
@@ -310,21 +320,39 @@ impl Word {
             color,
         }
     }
+
+    /// Dims the color of the word, e.g. to draw it as a background.
+    pub fn dimmed(mut self) -> Self {
+        self.color = match self.color {
+            Color::Rgb(r, g, b) => Color::Rgb(r / 3, g / 3, b / 3),
+            Color::LightRed => Color::Red,
+            Color::LightGreen => Color::Green,
+            Color::LightYellow => Color::Yellow,
+            Color::LightBlue => Color::Blue,
+            Color::LightMagenta => Color::Magenta,
+            Color::LightCyan => Color::Cyan,
+            _ => Color::DarkGray,
+        };
+        self
+    }
+
+    /// Recolors the word for the given color mode.
+    pub fn in_mode(mut self, mode: ColorMode) -> Self {
+        self.color = mode.apply(self.color);
+        self
+    }
+}
+
+impl Word {
+    /// Draws the word with the given renderer.
+    pub fn render<R: GameRenderer>(&self, renderer: &mut R) {
+        renderer.draw_text(&self.word, self.starting_point, self.factor, self.color);
+    }
 }
 
+#[cfg(feature = "tui")]
 impl Shape for Word {
     fn draw(&self, painter: &mut Painter) {
-        for (i, letter) in self.word.chars().enumerate() {
-            let letter = Letter::new_letter(
-                letter,
-                (
-                    self.starting_point.0 + (i as f64) * self.factor,
-                    self.starting_point.1,
-                ),
-                self.color,
-            );
-
-            letter.draw(painter);
-        }
+        self.render(painter);
     }
 }
@@ -0,0 +1,369 @@
+use crate::brick::{Brick, Slope};
+use crate::rectf64::Rectf64;
+use anyhow::Context;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+use std::io::Read;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The patterns the level generator picks from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Pattern {
+    /// Rows widening from the top, centered in the brick area.
+    Pyramid,
+    /// Alternating bricks, mirrored around the center.
+    Checkerboard,
+    /// Random columns on the left half, mirrored to the right half.
+    MirroredColumns,
+}
+
+const PATTERNS: [Pattern; 3] = [
+    Pattern::Pyramid,
+    Pattern::Checkerboard,
+    Pattern::MirroredColumns,
+];
+
+/// Generates a symmetric brick layout.
+///
+/// The layout is picked and shaped by `seed`, so the same seed always produces the same level.
+/// Higher difficulties fill more rows.
+///
+/// # Parameters
+/// - `seed`: The seed of the layout.
+/// - `difficulty`: The difficulty of the level, `0` being the easiest.
+/// - `region`: The area the bricks are placed in.
+/// - `brick_size`: The `(width, height)` of a single brick.
+///
+/// # Returns
+/// The bricks of the level.
+pub fn generate(seed: u64, difficulty: u8, region: &Rectf64, brick_size: (f64, f64)) -> Vec<Brick> {
+    let (width, height) = brick_size;
+    let cols = (region.width / width).floor() as usize;
+    let rows = ((region.height / height).floor() as usize).min(2 + difficulty as usize);
+    if cols == 0 || rows == 0 {
+        return vec![];
+    }
+    let origin_x = region.x + (region.width - cols as f64 * width) / 2.;
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let pattern = PATTERNS[rng.gen_range(0..PATTERNS.len())];
+    // Probability of a brick in a cell of the mirrored columns pattern.
+    let density = (0.4 + 0.1 * difficulty as f64).min(0.9);
+
+    let mut bricks = vec![];
+    for row in 0..rows {
+        let left_half: Vec<bool> = (0..cols.div_ceil(2))
+            .map(|col| match pattern {
+                Pattern::Pyramid => cols / 2 - col <= row + 1,
+                Pattern::Checkerboard => (row + col) % 2 == 0,
+                Pattern::MirroredColumns => rng.gen_bool(density),
+            })
+            .collect();
+        for col in 0..cols {
+            // Mirror the left half of the row around the center.
+            if left_half[col.min(cols - 1 - col)] {
+                bricks.push(Brick::new(Rectf64 {
+                    x: origin_x + col as f64 * width,
+                    y: region.top() - (row + 1) as f64 * height,
+                    width,
+                    height,
+                }));
+            }
+        }
+    }
+    bricks
+}
+
+/// A fixed level, read from a level file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    /// The bricks of the level.
+    pub bricks: Vec<Brick>,
+    /// The gaps in the top wall, horizontal ranges `(start, end)` in game coordinates.
+    pub top_gaps: Vec<(f64, f64)>,
+}
+
+/// Parses a range, e.g. a top wall gap, given as `START:END`.
+///
+/// # Parameters
+/// - `s`: The range to parse.
+///
+/// # Returns
+/// The `(start, end)` of the range, or an error if it's malformed or empty.
+pub fn parse_range(s: &str) -> Result<(f64, f64), String> {
+    let (start, end) = s
+        .split_once(':')
+        .ok_or_else(|| format!("expected START:END, got `{s}`"))?;
+    let start: f64 = start.trim().parse().map_err(|e| format!("{e}"))?;
+    let end: f64 = end.trim().parse().map_err(|e| format!("{e}"))?;
+    if start >= end {
+        return Err(format!("range start must be less than its end, got `{s}`"));
+    }
+    Ok((start, end))
+}
+
+/// Parses a level: an optional line opening gaps in the top wall, e.g. `gaps: 40:60, 300:320`,
+/// followed by the brick layout drawn as a grid of characters.
+///
+/// Every line is a row of bricks, the first line being the top row. A `#` is a brick, a digit
+/// from `1` to `9` a brick taking that many hits, a `/` or a `\` a brick sloped along that
+/// diagonal and a `.` or a space an empty cell. The grid is centered horizontally in `region`
+/// and placed at its top. The gaps are given as `START:END` ranges in game coordinates, like
+/// `--top-gap`.
+///
+/// # Parameters
+/// - `text`: The level.
+/// - `region`: The area the bricks are placed in.
+/// - `brick_size`: The `(width, height)` of a single brick.
+///
+/// # Returns
+/// The level, or an error if a gap is malformed, or the grid has an unknown character, has no
+/// bricks or doesn't fit in `region`.
+pub fn parse(text: &str, region: &Rectf64, brick_size: (f64, f64)) -> anyhow::Result<Level> {
+    let (width, height) = brick_size;
+    let cols = (region.width / width).floor() as usize;
+    let rows = (region.height / height).floor() as usize;
+    let origin_x = region.x + (region.width - cols as f64 * width) / 2.;
+
+    let mut lines = text.lines().peekable();
+    let top_gaps = match lines
+        .peek()
+        .and_then(|line| line.trim().strip_prefix("gaps:"))
+    {
+        Some(gaps) => {
+            lines.next();
+            gaps.split(',')
+                .map(|gap| parse_range(gap).map_err(|e| anyhow::anyhow!("bad gap: {e}")))
+                .collect::<anyhow::Result<_>>()?
+        }
+        None => vec![],
+    };
+    let grid: Vec<&str> = lines.map(str::trim_end).collect();
+    let grid = match grid.iter().rposition(|line| !line.is_empty()) {
+        Some(last) => &grid[..=last],
+        None => anyhow::bail!("the level has no bricks"),
+    };
+    anyhow::ensure!(
+        grid.len() <= rows,
+        "the level has {} rows, at most {rows} fit",
+        grid.len()
+    );
+
+    let mut bricks = vec![];
+    for (row, line) in grid.iter().enumerate() {
+        let len = line.chars().count();
+        anyhow::ensure!(
+            len <= cols,
+            "row {} has {len} columns, at most {cols} fit",
+            row + 1
+        );
+        for (col, cell) in line.chars().enumerate() {
+            let (hp, slope) = match cell {
+                '.' | ' ' => continue,
+                '#' => (1, None),
+                '1'..='9' => (cell as u8 - b'0', None),
+                '/' => (1, Some(Slope::Rising)),
+                '\\' => (1, Some(Slope::Falling)),
+                _ => anyhow::bail!("unknown cell {cell:?} in row {}", row + 1),
+            };
+            let brick = Brick::new(Rectf64 {
+                x: origin_x + col as f64 * width,
+                y: region.top() - (row + 1) as f64 * height,
+                width,
+                height,
+            })
+            .with_hp(hp);
+            bricks.push(match slope {
+                Some(slope) => brick.sloped(slope),
+                None => brick,
+            });
+        }
+    }
+    anyhow::ensure!(!bricks.is_empty(), "the level has no bricks");
+    Ok(Level { bricks, top_gaps })
+}
+
+/// Reads a brick layout, see [`parse`] for its format, e.g. from a level file or the standard
+/// input.
+///
+/// # Parameters
+/// - `source`: Where to read the level from.
+/// - `region`: The area the bricks are placed in.
+/// - `brick_size`: The `(width, height)` of a single brick.
+///
+/// # Returns
+/// The level, or an error if the level can't be read or is malformed.
+pub fn load<R: Read>(
+    mut source: R,
+    region: &Rectf64,
+    brick_size: (f64, f64),
+) -> anyhow::Result<Level> {
+    let mut text = String::new();
+    source
+        .read_to_string(&mut text)
+        .context("failed to read the level")?;
+    parse(&text, region, brick_size).context("malformed level")
+}
+
+/// A day of the daily challenge, counted in days since the Unix epoch, in UTC.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Day(pub u64);
+
+impl Day {
+    /// Returns the current day in UTC.
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+        Self(secs / 86_400)
+    }
+
+    /// Returns the level seed of the day, the same for everyone playing on that day.
+    pub fn seed(&self) -> u64 {
+        // SplitMix64, so that consecutive days get unrelated seeds.
+        let mut z = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+impl fmt::Display for Day {
+    /// Formats the day as an ISO 8601 date, e.g. `2024-07-28`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Howard Hinnant's `civil_from_days`.
+        let z = self.0 as i64 + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+        write!(f, "{year:04}-{month:02}-{day:02}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ball::EllasticCollision;
+
+    const REGION: Rectf64 = Rectf64 {
+        x: 2.,
+        y: 90.,
+        width: 356.,
+        height: 88.,
+    };
+
+    #[test]
+    fn test_generated_layout_is_within_region() {
+        for seed in 0..20 {
+            let bricks = generate(seed, 3, &REGION, (14., 5.));
+            assert!(!bricks.is_empty());
+            for brick in bricks {
+                let area = brick.area();
+                assert!(area.left() >= REGION.left() && area.right() <= REGION.right());
+                assert!(area.bottom() >= REGION.bottom() && area.top() <= REGION.top());
+            }
+        }
+    }
+
+    #[test]
+    fn test_generated_layout_is_symmetric() {
+        let center = REGION.x + REGION.width / 2.;
+        for seed in 0..20 {
+            let bricks: Vec<_> = generate(seed, 3, &REGION, (14., 5.))
+                .iter()
+                .map(|brick| brick.area())
+                .collect();
+            for area in &bricks {
+                let mirrored = 2. * center - area.right();
+                assert!(bricks
+                    .iter()
+                    .any(|other| (other.x - mirrored).abs() < 1e-9 && other.y == area.y));
+            }
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_layout() {
+        assert_eq!(
+            generate(42, 2, &REGION, (14., 5.)),
+            generate(42, 2, &REGION, (14., 5.))
+        );
+    }
+
+    #[test]
+    fn test_parse_level() {
+        let bricks = parse("#..2\n\n.#\n", &REGION, (14., 5.)).unwrap().bricks;
+        // 25 columns fit, leaving 3 units on each side.
+        let cells: Vec<_> = bricks
+            .iter()
+            .map(|brick| (brick.area().x, brick.area().y, brick.hp()))
+            .collect();
+        assert_eq!(cells, [(5., 173., 1), (47., 173., 2), (19., 163., 1)]);
+
+        assert!(parse("#x#", &REGION, (14., 5.)).is_err());
+        assert!(parse("...\n\n", &REGION, (14., 5.)).is_err());
+        assert!(parse(&"#".repeat(26), &REGION, (14., 5.)).is_err());
+        assert!(parse(&"#\n".repeat(18), &REGION, (14., 5.)).is_err());
+    }
+
+    #[test]
+    fn test_parse_slopes() {
+        let bricks = parse("/.\\", &REGION, (14., 5.)).unwrap().bricks;
+        let area = |x| Rectf64 {
+            x,
+            y: 173.,
+            width: 14.,
+            height: 5.,
+        };
+        assert_eq!(
+            bricks,
+            [
+                Brick::new(area(5.)).sloped(Slope::Rising),
+                Brick::new(area(33.)).sloped(Slope::Falling),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_gaps() {
+        let level = parse("gaps: 40:60, 300:320\n#", &REGION, (14., 5.)).unwrap();
+        assert_eq!(level.top_gaps, [(40., 60.), (300., 320.)]);
+        assert_eq!(level.bricks.len(), 1);
+        // The bricks start right below the gaps.
+        assert_eq!(level.bricks[0].area().y, 173.);
+
+        assert!(parse("gaps: 60:40\n#", &REGION, (14., 5.)).is_err());
+        assert!(parse("gaps: 40\n#", &REGION, (14., 5.)).is_err());
+    }
+
+    #[test]
+    fn test_load_level() {
+        let level = load("#.\n.2\n".as_bytes(), &REGION, (14., 5.)).unwrap();
+        assert_eq!(level.bricks.len(), 2);
+        assert!(level.top_gaps.is_empty());
+        let error = load("#x\n".as_bytes(), &REGION, (14., 5.)).unwrap_err();
+        assert!(format!("{error:#}").starts_with("malformed level"));
+    }
+
+    #[test]
+    fn test_daily_seed() {
+        let day = Day(19_932);
+        assert_eq!(day.to_string(), "2024-07-28");
+        assert_eq!(Day(0).to_string(), "1970-01-01");
+        assert_eq!(Day(11_016).to_string(), "2000-02-29");
+
+        assert_eq!(day.seed(), Day(19_932).seed());
+        assert_ne!(day.seed(), Day(19_933).seed());
+        assert_eq!(
+            generate(day.seed(), 2, &REGION, (14., 5.)),
+            generate(Day(19_932).seed(), 2, &REGION, (14., 5.))
+        );
+    }
+}
@@ -0,0 +1,23 @@
+//! The Arkanoid engine, driven by [`GameEvent`]s and observed through [`Game`]'s accessors,
+//! e.g. [`Game::state`], so that it can run headlessly.
+//!
+//! The shapes are drawn through a [`render::GameRenderer`]. Drawing them on a ratatui canvas
+//! needs the `tui` feature, enabled by default.
+
+pub mod ball;
+pub mod bottom;
+pub mod brick;
+pub mod color;
+pub mod game;
+pub mod letters;
+pub mod levels;
+pub mod paddle;
+pub mod powerup;
+pub mod rectf64;
+pub mod render;
+pub mod timestep;
+pub mod trace;
+pub mod walls;
+
+pub use game::{Game, GameEvent, GameOptions, GameState};
+pub use paddle::Direction;
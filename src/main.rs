@@ -1,32 +1,38 @@
-mod ball;
-mod bottom;
-mod brick;
-mod game;
-mod letters;
-mod paddle;
-mod rectf64;
-mod walls;
-
-use crate::game::{GameEvent, GameOptions};
-use crate::paddle::Direction;
-#[cfg(feature = "debug")]
+mod export;
+mod input;
+mod scores;
+
+use crate::input::{key_name, Action, Command, Debouncer, Keymap};
+use crate::scores::HighScores;
 use anyhow::Context;
+use arkanoid_tui::ball::{Ball, EllasticCollision};
+use arkanoid_tui::color::ColorMode;
+use arkanoid_tui::game::{combo_points, InitialDir, LivesDisplay};
+use arkanoid_tui::levels::{parse_range, Day};
+use arkanoid_tui::paddle;
+use arkanoid_tui::rectf64::Rectf64;
+use arkanoid_tui::timestep::{self, FixedTimestep};
+use arkanoid_tui::trace::TraceWriter;
+use arkanoid_tui::{Direction, Game, GameEvent, GameOptions, GameState};
 use clap::Parser;
-use crossterm::event::{KeyCode, KeyEventKind};
+use crossterm::event::{
+    DisableMouseCapture, EnableMouseCapture, KeyCode, KeyEventKind, KeyModifiers, MouseEventKind,
+};
 use crossterm::terminal::{
     disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
 };
 use crossterm::{event, ExecutableCommand};
 use ratatui::backend::CrosstermBackend;
-use ratatui::prelude::{style::Stylize, Color, Constraint, Layout, Rect};
+use ratatui::buffer::Buffer;
+use ratatui::prelude::{style::Stylize, Color, Constraint, Layout, Line, Modifier, Rect};
 use ratatui::symbols::Marker;
-use ratatui::widgets::canvas::Canvas;
-use ratatui::widgets::Paragraph;
+use ratatui::widgets::canvas::{Canvas, Points};
+use ratatui::widgets::{Block, Clear, Paragraph, Widget};
 use ratatui::Terminal;
-#[cfg(feature = "debug")]
 use std::fs::File;
-use std::io::stdout;
-use std::time::Duration;
+use std::io::{stdout, BufWriter, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 #[cfg(feature = "debug")]
 use tracing::Level;
 #[cfg(feature = "debug")]
@@ -36,6 +42,24 @@ use tracing_appender::non_blocking::WorkerGuard;
 #[cfg(feature = "debug")]
 use tracing_subscriber::EnvFilter;
 
+/// How long a status message replaces the controls help.
+const STATUS_DURATION: Duration = Duration::from_secs(2);
+
+/// The width to height ratio of the playfield, in game coordinates.
+const PLAYFIELD_ASPECT: f64 = 2.0;
+
+/// How many times taller than wide a terminal cell usually is.
+const CELL_ASPECT: f64 = 2.0;
+
+/// The width of the playfield, in game coordinates.
+const PLAYFIELD_WIDTH: f64 = 360.0;
+
+/// The width and height, in cells, of the minimap, its border included.
+const MINIMAP_SIZE: (u16, u16) = (26, 9);
+
+/// How often the FPS shown with `--show-fps` is updated.
+const FPS_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Parser, Debug, Default)]
 #[command(author, version, about, long_about = None)]
 #[command(propagate_version = true)]
@@ -43,17 +67,302 @@ struct ArkanoidOpts {
     /// Number of bricks, rendered by the game
     #[arg(long, default_value_t = 10)]
     brick_count: u16,
+    /// Generate a symmetric brick layout from the given seed instead of scattering bricks
+    #[arg(long)]
+    generate: Option<u64>,
+    /// Seed the game's randomness, e.g. the scattered bricks, to replay the same game
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Play the challenge of the day: a generated layout that's the same for everyone on the
+    /// same (UTC) day
+    #[arg(long, action, conflicts_with = "generate")]
+    daily: bool,
+    /// Difficulty of the generated layout
+    #[arg(long, default_value_t = 1)]
+    difficulty: u8,
     /// Possible marker value: Dot, Braille, Bar, Block, HalfBlock
     #[arg(long, default_value_t = Marker::HalfBlock)]
     marker: Marker,
     /// Game FPS
     #[arg(long, default_value_t = 24)]
     fps: u16,
+    /// Render as fast as possible instead of at the FPS, the simulation still runs at the
+    /// step rate
+    #[arg(long, action)]
+    uncapped: bool,
+    /// Highest FPS rendered at while uncapped, to spare the CPU
+    #[arg(long, value_name = "FPS", requires = "uncapped")]
+    max_fps: Option<u16>,
+    /// Show the FPS achieved in the title
+    #[arg(long, action)]
+    show_fps: bool,
+    /// Simulation steps per second, independent of the FPS
+    #[arg(long, default_value_t = timestep::STEP_RATE, value_name = "HZ")]
+    step_rate: u16,
+    /// Longest simulation step in milliseconds, bigger frame deltas are clamped
+    #[arg(long, default_value_t = 100)]
+    max_frame_delta: u64,
+    /// Direction the ball is launched in
+    #[arg(long, value_enum, default_value_t = InitialDir::UpRight)]
+    initial_direction: InitialDir,
+    /// Share of the ball's velocity kept after bouncing off a wall or a brick, the paddle
+    /// bringing it back to its launch speed
+    #[arg(long, default_value_t = 1.0)]
+    restitution: f64,
+    /// Speed, in units per second, the ball gains for every brick broken
+    #[arg(long, default_value_t = 0.0)]
+    speed_per_brick: f64,
+    /// Score every brick by the number of bricks broken since the ball last touched the
+    /// paddle, instead of a point each
+    #[arg(long, action)]
+    combo_scoring: bool,
+    /// Speed, in units per second, the ball gains every time it hits the paddle
+    #[arg(long, default_value_t = 0.0)]
+    speed_ramp: f64,
+    /// Top speed, in units per second, broken bricks and the speed ramp can speed the ball up
+    /// to
+    #[arg(long)]
+    max_ball_speed: Option<f64>,
+    /// Time, in milliseconds, the ball takes to accelerate to full speed after launch
+    #[arg(long, default_value_t = 0)]
+    ease_in: u64,
+    /// Ignore a held pause, quit or restart key for this long, in milliseconds
+    #[arg(long, value_name = "MS", default_value_t = input::DEBOUNCE_MS)]
+    debounce: u64,
+    /// Share of the paddle around its center that bounces the ball without spin (0..1)
+    #[arg(long, default_value_t = 0.0)]
+    paddle_deadzone: f64,
+    /// How strongly the angle of impact turns the paddle's movement into spin
+    #[arg(long, default_value_t = paddle::ANGULAR_FACTOR)]
+    paddle_angular_factor: f64,
+    /// Mass of the paddle relative to the ball, heavier paddles give more spin
+    #[arg(long, default_value_t = paddle::MASS_FACTOR)]
+    paddle_mass_factor: f64,
+    /// Friction between the ball and the paddle
+    #[arg(long, default_value_t = paddle::FRICTION)]
+    paddle_friction: f64,
+    /// Bounce the ball off the paddle at up to this angle from vertical, in degrees, steeper
+    /// the further from the center it hits, instead of spinning it
+    #[arg(long, value_name = "DEGREES")]
+    paddle_bounce_angle: Option<f64>,
+    /// Least share of its speed the ball moves vertically with after a bounce (0..1)
+    #[arg(long, default_value_t = 0.0)]
+    min_vertical_speed: f64,
+    /// Height above the paddle at which the ball is already caught
+    #[arg(long, default_value_t = paddle::CATCH_TOLERANCE)]
+    paddle_catch_tolerance: f64,
+    /// Start the paddle, and with it the ball, at a random position
+    #[arg(long, action)]
+    random_paddle_start: bool,
+    /// Mark where the descending ball will reach the paddle
+    #[arg(long, action)]
+    aim_assist: bool,
+    /// Show an overview of the bricks and the ball in a corner
+    #[arg(long, action)]
+    minimap: bool,
+    /// Set the ball on fire after breaking this many bricks without touching the paddle
+    #[arg(long, value_name = "BRICKS", default_value_t = 0)]
+    fire_streak: usize,
+    /// Drop the bricks above a cleared row down into it
+    #[arg(long, action)]
+    compaction: bool,
+    /// Hide the bricks until the ball comes close to them
+    #[arg(long, action)]
+    fog: bool,
+    /// Briefly highlight where the ball hit the paddle
+    #[arg(long, action)]
+    paddle_tilt: bool,
+    /// Draw a motion blur streak behind the ball
+    #[arg(long, action)]
+    motion_blur: bool,
+    /// Draw every brick in a random color
+    #[arg(long, action)]
+    random_brick_colors: bool,
+    /// Draw the paddle with rounded ends
+    #[arg(long, action)]
+    rounded_paddle: bool,
+    /// Survival mode: rows of bricks keep descending towards the paddle
+    #[arg(long, action)]
+    invasion: bool,
+    /// How many times destroyed bricks split into two smaller halves (at most 2)
+    #[arg(long, default_value_t = 0)]
+    split_depth: u8,
+    /// Text drawn faintly in the background of the playfield
+    #[arg(long)]
+    background: Option<String>,
+    /// Largest random deflection, in degrees, of the ball bouncing off a brick
+    #[arg(long, default_value_t = 0.0)]
+    bounce_jitter: f64,
+    /// Don't animate the bricks while the game is paused
+    #[arg(long, action)]
+    no_pulse: bool,
+    /// Quit on `q` right away, without asking for confirmation
+    #[arg(long, action)]
+    no_confirm_quit: bool,
+    /// Duration, in milliseconds, of the animation played when the ball is lost
+    #[arg(long, default_value_t = 1000)]
+    death_animation: u64,
+    /// Write the ball position, score and state after every tick to this file
+    #[arg(long, value_name = "FILE")]
+    trace_out: Option<PathBuf>,
+    /// Number of balls that can be lost before the game is over
+    #[arg(long, default_value_t = 3)]
+    lives: u8,
+    /// Widen the paddle a little for every life lost on a level
+    #[arg(long, action)]
+    mercy: bool,
+    /// How the lives left are shown
+    #[arg(long, value_enum, default_value_t = LivesDisplay::Number)]
+    lives_display: LivesDisplay,
+    /// Number of levels, each with more bricks and a faster ball, to clear to win
+    #[arg(long, default_value_t = 1)]
+    levels: u8,
+    /// Read a fixed brick layout from this file, `-` for the standard input, a grid of `#`
+    /// for a brick, `1`-`9` for a brick taking that many hits, `/` and `\` for a brick sloped
+    /// along that diagonal and `.` for an empty cell, optionally below a line opening gaps in
+    /// the top wall, e.g. `gaps: 40:60, 300:320`
+    #[arg(long, value_name = "FILE")]
+    level_file: Option<PathBuf>,
+    /// Most hits a brick takes before it breaks, each brick getting a random number of them
+    #[arg(long, default_value_t = 1, value_name = "HITS")]
+    max_brick_hp: u8,
+    /// Colors of the multi-hit bricks by the hits they have left, from a single one up,
+    /// e.g. yellow,#ffa500,red
+    #[arg(long, value_name = "COLORS", value_delimiter = ',')]
+    durability_colors: Option<Vec<Color>>,
+    /// Show the hits a multi-hit brick has left on it
+    #[arg(long, action)]
+    hp_digits: bool,
+    /// Show a health bar along the top of a multi-hit brick
+    #[arg(long, action)]
+    health_bars: bool,
+    /// Hold the ball on the paddle at the start of every life until SPACE launches it
+    #[arg(long, action)]
+    sticky_launch: bool,
+    /// Chance of a destroyed brick dropping a power-up to catch with the paddle (0..1)
+    #[arg(long, default_value_t = 0.0)]
+    powerup_chance: f64,
+    /// Time, in seconds, a caught power-up resizes the paddle for
+    #[arg(long, default_value_t = 10.0, value_name = "SECS")]
+    powerup_duration: f64,
+    /// Most balls in play at once, however often they split
+    #[arg(long, default_value_t = 8, value_name = "COUNT")]
+    max_balls: usize,
+    /// Chance of a ball splitting in two when it bounces off a wall (0..1)
+    #[arg(long, default_value_t = 0.0)]
+    wall_split_chance: f64,
+    /// Make the balls bounce off each other instead of passing through
+    #[arg(long, action)]
+    ball_collisions: bool,
+    /// Score every game starts with
+    #[arg(long, default_value_t = 0)]
+    starting_score: usize,
+    /// Keep adding up the score across restarts until quitting
+    #[arg(long, action)]
+    cumulative_score: bool,
+    /// Bounce the ball off the bottom instead of losing it
+    #[arg(long, action)]
+    bottom_bounces: bool,
+    /// End the game after this many seconds of play
+    #[arg(long, value_name = "SECS")]
+    time_limit: Option<f64>,
+    /// Nudge the ball down after this many seconds without hitting the paddle or a brick
+    #[arg(long, value_name = "SECS")]
+    max_airtime: Option<f64>,
+    /// Restart automatically this many seconds after the game is won or lost, until a key is
+    /// pressed
+    #[arg(long, value_name = "SECS")]
+    auto_restart: Option<u64>,
+    /// Draw without colors, telling the objects apart by their shapes. Turned on by default
+    /// when `NO_COLOR` is set or the terminal is a dumb one
+    #[arg(long, action)]
+    no_color: bool,
+    /// Dim every other row of the playfield, like the scanlines of a CRT. Ignored when
+    /// drawing without colors
+    #[arg(long, action)]
+    crt: bool,
+    /// Keep the playfield's proportions, leaving empty margins around it
+    #[arg(long, action)]
+    letterbox: bool,
+    /// Don't draw a frame around the playfield
+    #[arg(long, action)]
+    no_border: bool,
+    /// Open a gap in the top wall, given as START:END in game coordinates (0..360).
+    /// Can be repeated, and adds to the gaps of the level file.
+    #[arg(long = "top-gap", value_parser = parse_range)]
+    top_gaps: Vec<(f64, f64)>,
+    /// Place the bricks between these heights, given as BOTTOM:TOP in game coordinates
+    /// (0..180), instead of in the upper half
+    #[arg(long, value_name = "BOTTOM:TOP", value_parser = parse_range)]
+    brick_rows: Option<(f64, f64)>,
+    /// Size of a brick, given as WIDTHxHEIGHT in game coordinates
+    #[arg(long, value_name = "WIDTHxHEIGHT", default_value = "14x5", value_parser = parse_size)]
+    brick_size: (f64, f64),
+    /// Thickness of the walls, given as the width of the side walls and the height of the
+    /// top wall, WIDTHxHEIGHT
+    #[arg(long, value_name = "WIDTHxHEIGHT", default_value = "2x2", value_parser = parse_size)]
+    wall_thickness: (f64, f64),
+    /// File the current frame is saved to, as ANSI-colored text, when the export key is pressed
+    #[arg(long, default_value = "arkanoid-frame.ans")]
+    export_path: PathBuf,
+    /// Let the game play itself, the paddle following the ball
+    #[arg(long, action)]
+    demo: bool,
+    /// Don't move the paddle with the mouse
+    #[arg(long, action)]
+    no_mouse: bool,
+    /// Read the key bindings from this TOML file, e.g. `left = "a"`. Keys are single
+    /// characters or one of left, right, up, down, space, enter, tab, backspace and esc
+    #[arg(long, value_name = "FILE")]
+    keymap: Option<PathBuf>,
+    /// Key moving the paddle to the left [default: left]
+    #[arg(long, value_name = "KEY", value_parser = input::parse_key)]
+    key_left: Option<KeyCode>,
+    /// Key moving the paddle to the right [default: right]
+    #[arg(long, value_name = "KEY", value_parser = input::parse_key)]
+    key_right: Option<KeyCode>,
+    /// Key launching the ball [default: space]
+    #[arg(long, value_name = "KEY", value_parser = input::parse_key)]
+    key_launch: Option<KeyCode>,
+    /// Key pausing the game [default: enter]
+    #[arg(long, value_name = "KEY", value_parser = input::parse_key)]
+    key_pause: Option<KeyCode>,
+    /// Key restarting the game [default: tab]
+    #[arg(long, value_name = "KEY", value_parser = input::parse_key)]
+    key_restart: Option<KeyCode>,
+    /// Key quitting the game [default: q]
+    #[arg(long, value_name = "KEY", value_parser = input::parse_key)]
+    key_quit: Option<KeyCode>,
+    /// Key confirming to quit, besides the quit key [default: y]
+    #[arg(long, value_name = "KEY", value_parser = input::parse_key)]
+    key_confirm: Option<KeyCode>,
+    /// Key saving the current frame to the export path [default: e]
+    #[arg(long, value_name = "KEY", value_parser = input::parse_key)]
+    key_export: Option<KeyCode>,
+    /// Key saving the game to the save path [default: s]
+    #[arg(long, value_name = "KEY", value_parser = input::parse_key)]
+    key_save: Option<KeyCode>,
+    /// Key switching markers [default: m]
+    #[arg(long, value_name = "KEY", value_parser = input::parse_key)]
+    key_marker: Option<KeyCode>,
+    /// File the game is saved to when the save key is pressed
+    #[arg(long, default_value = "arkanoid-save.json")]
+    save_path: PathBuf,
+    /// Resume the game saved to this file instead of starting a new one
+    #[arg(long, value_name = "FILE")]
+    resume: Option<PathBuf>,
+    /// File the high scores are kept in across runs [default: ~/.arkanoid-tui/scores.json]
+    #[arg(long, value_name = "FILE")]
+    scores_file: Option<PathBuf>,
     #[cfg(feature = "debug")]
     /// Enable tracing and debug logging
     #[arg(long, action)]
     tracing: bool,
     #[cfg(feature = "debug")]
+    /// Show the most recent simulation effects in a side panel
+    #[arg(long, action)]
+    debug_overlay: bool,
+    #[cfg(feature = "debug")]
     /// Manual ball
     #[arg(long, action)]
     manual_ball: bool,
@@ -70,71 +379,307 @@ fn main() -> anyhow::Result<()> {
         _tracing_guard = Some(init_tracing()?);
     }
 
-    stdout().execute(EnterAlternateScreen)?;
-    enable_raw_mode()?;
-    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
-    terminal.clear()?;
+    let mut keymap = match &opts.keymap {
+        Some(path) => Keymap::load(path)?,
+        None => Keymap::default(),
+    };
+    for (key, binding) in [
+        (opts.key_left, &mut keymap.left),
+        (opts.key_right, &mut keymap.right),
+        (opts.key_launch, &mut keymap.launch),
+        (opts.key_pause, &mut keymap.pause),
+        (opts.key_restart, &mut keymap.restart),
+        (opts.key_quit, &mut keymap.quit),
+        (opts.key_confirm, &mut keymap.confirm),
+        (opts.key_export, &mut keymap.export),
+        (opts.key_save, &mut keymap.save),
+        (opts.key_marker, &mut keymap.marker),
+    ] {
+        if let Some(key) = key {
+            *binding = key;
+        }
+    }
+    keymap.check().context("conflicting key bindings")?;
+    let controls = keymap.help();
 
-    let game_options = GameOptions::default()
+    let color_mode = if opts.no_color {
+        ColorMode::Monochrome
+    } else {
+        ColorMode::detect()
+    };
+    let mut game_options = GameOptions::default()
         .paddle_color(Color::LightGreen)
         .walls_color(Color::Blue)
-        .ball_speed(2.)
+        .ball_speed(48.)
         .area(Rect::new(0, 0, 360, 180).into())
-        .brick_count(opts.brick_count);
-    let mut game = game_options.clone().build();
+        .brick_count(opts.brick_count)
+        .top_wall_gaps(opts.top_gaps.clone())
+        .restitution(opts.restitution)
+        .speed_per_brick(opts.speed_per_brick)
+        .speed_ramp(opts.speed_ramp)
+        .initial_direction(opts.initial_direction)
+        .ball_ease_in(Duration::from_millis(opts.ease_in).as_secs_f64())
+        .paddle_deadzone(opts.paddle_deadzone)
+        .paddle_angular_factor(opts.paddle_angular_factor)
+        .paddle_mass_factor(opts.paddle_mass_factor)
+        .paddle_friction(opts.paddle_friction)
+        .paddle_catch_tolerance(opts.paddle_catch_tolerance)
+        .paddle_tilt(opts.paddle_tilt)
+        .rounded_paddle(opts.rounded_paddle)
+        .random_brick_colors(opts.random_brick_colors)
+        .motion_blur(opts.motion_blur)
+        .fog(opts.fog)
+        .compaction(opts.compaction)
+        .bottom_bounces(opts.bottom_bounces)
+        .starting_score(opts.starting_score)
+        .lives(opts.lives)
+        .mercy(opts.mercy)
+        .lives_display(opts.lives_display)
+        .level_count(opts.levels)
+        .max_brick_hp(opts.max_brick_hp)
+        .brick_hp_digits(opts.hp_digits)
+        .brick_health_bars(opts.health_bars)
+        .sticky_launch(opts.sticky_launch)
+        .min_vertical_speed(opts.min_vertical_speed)
+        .powerup_chance(opts.powerup_chance)
+        .powerup_duration(opts.powerup_duration)
+        .max_balls(opts.max_balls)
+        .wall_split_chance(opts.wall_split_chance)
+        .ball_collisions(opts.ball_collisions)
+        .demo(opts.demo)
+        .aim_assist(opts.aim_assist)
+        .fire_streak(opts.fire_streak)
+        .ball_max_airtime(opts.max_airtime)
+        .color_mode(color_mode)
+        .random_paddle_start(opts.random_paddle_start)
+        .brick_pulse(!opts.no_pulse)
+        .invasion_mode(opts.invasion)
+        .split_depth(opts.split_depth)
+        .brick_size(opts.brick_size.0, opts.brick_size.1)
+        .wall_thickness(opts.wall_thickness.0, opts.wall_thickness.1)
+        .bounce_jitter(opts.bounce_jitter.to_radians())
+        .death_animation(Duration::from_millis(opts.death_animation).as_secs_f64())
+        .max_frame_delta(Duration::from_millis(opts.max_frame_delta).as_secs_f64());
+    if opts.combo_scoring {
+        game_options = game_options.scoring(combo_points);
+    }
+    if let Some(seed) = opts.generate {
+        game_options = game_options.generator(seed, opts.difficulty);
+    }
+    if let Some(path) = &opts.level_file {
+        // `-` reads the level from the standard input, which must be piped in.
+        if path == Path::new("-") {
+            let mut stdin = std::io::stdin();
+            anyhow::ensure!(
+                !stdin.is_terminal(),
+                "--level-file - reads the level from the standard input, pipe it in, \
+                 e.g. `arkanoid-tui --level-file - < level.txt`"
+            );
+            let mut layout = String::new();
+            stdin
+                .read_to_string(&mut layout)
+                .context("failed to read the level from the standard input")?;
+            game_options = game_options.level_layout(layout);
+        } else {
+            game_options = game_options.level_file(path.clone());
+        }
+    }
+    if let Some(seed) = opts.seed {
+        game_options = game_options.seed(seed);
+    }
+    if let Some(angle) = opts.paddle_bounce_angle {
+        game_options = game_options.paddle_bounce_angle(angle.to_radians());
+    }
+    // The daily challenge must be the same for everyone, so nothing random is left in it.
+    let daily = opts.daily.then(Day::today);
+    if let Some(day) = daily {
+        game_options = game_options
+            .generator(day.seed(), opts.difficulty)
+            .seed(day.seed())
+            .random_paddle_start(false)
+            .initial_direction(InitialDir::UpRight)
+            .bounce_jitter(0.);
+    }
+    if let Some((bottom, top)) = opts.brick_rows {
+        game_options = game_options.brick_region(Rectf64 {
+            x: 0.,
+            y: bottom,
+            width: 360.,
+            height: top - bottom,
+        });
+    }
+    if let Some(colors) = &opts.durability_colors {
+        game_options = game_options.durability_ramp(colors.clone());
+    }
+    if let Some(v) = opts.max_ball_speed {
+        game_options = game_options.max_ball_speed(v);
+    }
+    if let Some(limit) = opts.time_limit {
+        game_options = game_options.time_limit(limit);
+    }
+    if let Some(text) = &opts.background {
+        game_options = game_options.background(text.clone());
+    }
+    // The game falls back to the random layout, a level asked for by the player must load.
+    game_options.check_level()?;
+    let mut trace = match &opts.trace_out {
+        Some(path) => Some(TraceWriter::new(BufWriter::new(File::create(path)?))?),
+        None => None,
+    };
+    let mut game = match &opts.resume {
+        Some(path) => Game::load(path)?,
+        None => game_options.clone().build(),
+    };
+    // The scoring isn't saved along with the game.
+    if opts.combo_scoring && opts.resume.is_some() {
+        game.set_scoring(combo_points);
+    }
+    if opts.cumulative_score && opts.resume.is_none() {
+        game.carry_score(0);
+    }
+
+    // Anything that can fail, e.g. reading the level, is done before the terminal is taken
+    // over, so the error is readable.
+    stdout().execute(EnterAlternateScreen)?;
+    if !opts.no_mouse {
+        stdout().execute(EnableMouseCapture)?;
+    }
+    enable_raw_mode()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+    terminal.clear()?;
     let mut pause = false;
+    let mut confirm_quit = false;
+    let scores_path = opts.scores_file.clone().or_else(scores::default_path);
+    let mut high_scores = scores_path.as_deref().map(scores::load).unwrap_or_default();
+    // Whether the score of the current game was recorded in the high-score table.
+    let mut recorded = false;
+    // The rank of the last score if it made the high-score table.
+    let mut celebration = None;
+    // Whether to save the next rendered frame to `opts.export_path`.
+    let mut export = false;
+    // Message shown in place of the controls help, and when it was set.
+    let mut status: Option<(String, Instant)> = None;
+    // When the current game ended, used to restart it automatically.
+    let mut game_over: Option<Instant> = None;
+    // Whether a key was pressed since the game ended, which cancels the automatic restart.
+    let mut interrupted = false;
+    // Without colors there's nothing to dim.
+    let crt = opts.crt && color_mode != ColorMode::Monochrome;
+    let mut marker = opts.marker;
+    let mut debouncer = Debouncer::new(Duration::from_millis(opts.debounce));
+    let mut last_frame = Instant::now();
+    // Where the playfield was last drawn, to map the mouse onto.
+    let mut playfield = Rect::default();
+    let mut timestep = FixedTimestep::new(opts.step_rate, timestep::MAX_CATCH_UP_STEPS);
+    let mut fps = FpsMeter::new(Instant::now());
 
     loop {
-        let tick = 1000 / opts.fps as u64;
-        let tick_duration = Duration::from_millis(tick);
+        // The time waited for below is only simulated if the game isn't paused meanwhile.
+        timestep.set_paused(pause || confirm_quit);
+        // Uncapped, the loop only waits for what's left of the soft cap's frame, if any.
+        let tick_duration = match (opts.uncapped, opts.max_fps) {
+            (false, _) => Duration::from_millis(1000 / opts.fps as u64),
+            (true, None) => Duration::ZERO,
+            (true, Some(cap)) => Duration::from_secs_f64(1. / f64::from(cap.max(1)))
+                .saturating_sub(last_frame.elapsed()),
+        };
         let mut next_event = None;
+        let mut restart = false;
 
         if event::poll(tick_duration)? {
-            if let event::Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            if let event::Event::Mouse(mouse) = &event {
+                if matches!(mouse.kind, MouseEventKind::Moved | MouseEventKind::Drag(_)) {
+                    if let Some(x) = mouse_x(mouse.column, playfield) {
+                        next_event = Some(GameEvent::MovePadTo { x });
+                    }
+                }
+            }
+            if let event::Event::Key(key) = event {
                 if key.kind == KeyEventKind::Press {
-                    match key.code {
-                        KeyCode::Char('q') => {
+                    interrupted |= game_over.is_some();
+                    // Ctrl+C always quits, and so does a second `q` while asked to confirm.
+                    let force_quit = key.code == KeyCode::Char('c')
+                        && key.modifiers.contains(KeyModifiers::CONTROL);
+                    if force_quit {
+                        break;
+                    }
+                    let now = Instant::now();
+                    match (keymap.command(key.code), key.code) {
+                        (Some(Command::Quit), _) if !debouncer.accept(Action::Quit, now) => {}
+                        (Some(Command::Pause), _) if !debouncer.accept(Action::Pause, now) => {}
+                        (Some(Command::Restart), _) if !debouncer.accept(Action::Restart, now) => {}
+                        (Some(Command::Quit | Command::Confirm), _) if confirm_quit => {
+                            break;
+                        }
+                        _ if confirm_quit => {
+                            confirm_quit = false;
+                        }
+                        (Some(Command::Quit), _) if opts.no_confirm_quit => {
                             break;
                         }
+                        (Some(Command::Quit), _) => {
+                            confirm_quit = true;
+                        }
                         #[cfg(feature = "debug")]
-                        KeyCode::Left if opts.manual_ball => {
+                        (Some(Command::MoveLeft), _) if opts.manual_ball => {
                             next_event = Some(GameEvent::MoveBallManual {
                                 direction: Direction::Left,
                             })
                         }
-                        KeyCode::Left => {
+                        (Some(Command::MoveLeft), _) => {
                             next_event = Some(GameEvent::MovePad {
                                 direction: Direction::Left,
                             });
                         }
                         #[cfg(feature = "debug")]
-                        KeyCode::Right if opts.manual_ball => {
+                        (Some(Command::MoveRight), _) if opts.manual_ball => {
                             next_event = Some(GameEvent::MoveBallManual {
                                 direction: Direction::Right,
                             });
                         }
-                        KeyCode::Right => {
+                        (Some(Command::MoveRight), _) => {
                             next_event = Some(GameEvent::MovePad {
                                 direction: Direction::Right,
                             });
                         }
+                        (Some(Command::Launch), _) => {
+                            next_event = Some(GameEvent::Launch);
+                        }
+                        (Some(Command::Restart), _) => {
+                            restart = true;
+                        }
+                        (Some(Command::Pause), _) if celebration.is_some() => {
+                            celebration = None;
+                        }
+                        (Some(Command::Pause), _) => {
+                            pause = !pause;
+                        }
                         #[cfg(feature = "debug")]
-                        KeyCode::Up if opts.manual_ball => {
+                        (_, KeyCode::Up) if opts.manual_ball => {
                             next_event = Some(GameEvent::MoveBallManual {
                                 direction: Direction::Up,
                             });
                         }
                         #[cfg(feature = "debug")]
-                        KeyCode::Down if opts.manual_ball => {
+                        (_, KeyCode::Down) if opts.manual_ball => {
                             next_event = Some(GameEvent::MoveBallManual {
                                 direction: Direction::Down,
                             });
                         }
-                        KeyCode::Tab => {
-                            game = game_options.clone().build();
+                        (Some(Command::Export), _) => {
+                            export = true;
                         }
-                        KeyCode::Enter => {
-                            pause = !pause;
+                        (Some(Command::Save), _) => {
+                            let message = match game.save(&opts.save_path) {
+                                Ok(()) => format!("Saved the game to {}", opts.save_path.display()),
+                                Err(e) => format!("Failed to save the game: {e:#}"),
+                            };
+                            status = Some((message, Instant::now()));
+                        }
+                        (Some(Command::Marker), _) => {
+                            marker = next_marker(marker);
+                            status = Some((format!("Marker: {marker}"), Instant::now()));
                         }
                         _ => {}
                     }
@@ -142,46 +687,413 @@ fn main() -> anyhow::Result<()> {
             }
         }
 
-        if !pause {
+        // A key press cuts the wait short, so the game advances by the time that really passed.
+        let now = Instant::now();
+        let dt = now.duration_since(last_frame).as_secs_f64();
+        last_frame = now;
+
+        if !pause && !confirm_quit {
             if let Some(event) = next_event {
                 game.event(event);
             }
-            #[cfg(feature = "debug")]
-            if !opts.manual_ball {
-                game.event(GameEvent::Tick);
+        }
+        #[cfg(feature = "debug")]
+        let steps = if opts.manual_ball {
+            0
+        } else {
+            timestep.advance(dt)
+        };
+        #[cfg(not(feature = "debug"))]
+        let steps = timestep.advance(dt);
+        for _ in 0..steps {
+            game.event(GameEvent::Tick {
+                dt: timestep.step(),
+            });
+            if let Some(trace) = trace.as_mut() {
+                trace.record(&game)?;
             }
-            #[cfg(not(feature = "debug"))]
-            game.event(GameEvent::Tick);
         }
+        if timestep.is_paused() {
+            game.idle(dt);
+        }
+        game.animate(dt);
 
-        terminal.draw(|frame| {
+        if !recorded && game.state() != GameState::Running {
+            recorded = true;
+            // The demo's scores aren't the player's.
+            celebration = if opts.demo {
+                None
+            } else {
+                high_scores.insert(game.score())
+            };
+            game_over = Some(Instant::now());
+            if let (Some(path), Some(_)) = (&scores_path, celebration) {
+                if let Err(e) = scores::save(path, &high_scores) {
+                    status = Some((
+                        format!("Failed to save the high scores: {e}"),
+                        Instant::now(),
+                    ));
+                }
+            }
+        }
+
+        if let (Some(secs), Some(since)) = (opts.auto_restart, game_over) {
+            restart |= !interrupted && since.elapsed() >= Duration::from_secs(secs);
+        }
+        if restart {
+            let session_score = game.session_score();
+            game = game_options.clone().build();
+            if opts.cumulative_score {
+                game.carry_score(session_score);
+            }
+            recorded = false;
+            celebration = None;
+            game_over = None;
+            interrupted = false;
+        }
+
+        if status
+            .as_ref()
+            .is_some_and(|(_, since)| since.elapsed() > STATUS_DURATION)
+        {
+            status = None;
+        }
+
+        let completed = terminal.draw(|frame| {
             let vertical =
                 Layout::vertical([Constraint::Percentage(99), Constraint::Percentage(2)]);
             let [game_area, controls_area] = vertical.areas(frame.size());
+            #[cfg(feature = "debug")]
+            let game_area = if opts.debug_overlay {
+                let horizontal = Layout::horizontal([Constraint::Min(0), Constraint::Length(28)]);
+                let [game_area, overlay_area] = horizontal.areas(game_area);
+                frame.render_widget(effects_panel(&game, overlay_area), overlay_area);
+                game_area
+            } else {
+                game_area
+            };
+            let game_area = if opts.letterbox {
+                letterboxed(game_area, PLAYFIELD_ASPECT * CELL_ASPECT)
+            } else {
+                game_area
+            };
+            let mut canvas = Canvas::default();
+            if !opts.no_border {
+                let mut title = if opts.cumulative_score {
+                    format!(
+                        " Arkanoid — Level {} — Score {} — Total {} ",
+                        game.level(),
+                        game.score(),
+                        game.session_score()
+                    )
+                } else {
+                    format!(
+                        " Arkanoid — Level {} — Score {} ",
+                        game.level(),
+                        game.score()
+                    )
+                };
+                if opts.combo_scoring && game.combo() > 1 {
+                    title.push_str(&format!("— Combo ×{} ", game.combo()));
+                }
+                if opts.speed_ramp > 0. || opts.speed_per_brick > 0. {
+                    title.push_str(&format!("— Speed {:.0} ", game.ball_speed()));
+                }
+                if let Some(day) = daily {
+                    title.push_str(&format!("— Daily {day}, seed {} ", day.seed()));
+                }
+                if opts.show_fps {
+                    title.push_str(&format!("— {:.0} FPS ", fps.fps()));
+                }
+                canvas = canvas.block(Block::bordered().title(title));
+            }
             frame.render_widget(
-                Canvas::default()
-                    .marker(opts.marker)
-                    .x_bounds([0.0, 360.0])
+                canvas
+                    .marker(marker)
+                    .x_bounds([0.0, PLAYFIELD_WIDTH])
                     .y_bounds([0.0, 180.0])
                     .paint(|ctx| {
                         ctx.draw(&game);
                     }),
                 game_area,
             );
+            let inner = if opts.no_border {
+                game_area
+            } else {
+                Block::bordered().inner(game_area)
+            };
+            playfield = inner;
+            if opts.minimap {
+                let width = MINIMAP_SIZE.0.min(inner.width);
+                let height = MINIMAP_SIZE.1.min(inner.height);
+                let popup = Rect::new(inner.right() - width, inner.y, width, height);
+                frame.render_widget(Clear, popup);
+                frame.render_widget(minimap(&game), popup);
+            }
+            if crt {
+                scanlines(frame.buffer_mut(), game_area);
+            }
+            let help = match &status {
+                Some((message, _)) => message.as_str(),
+                None => controls.as_str(),
+            };
             frame.render_widget(
-                Paragraph::new("\nUse ← → to move, TAB to restart, ↵ to pause.")
-                    .centered()
-                    .bold(),
+                Paragraph::new(format!("\n{help}")).centered().bold(),
                 controls_area,
             );
+            if let Some(rank) = celebration {
+                let popup = centered(game_area, 30, high_scores.entries().len() as u16 + 6);
+                frame.render_widget(Clear, popup);
+                frame.render_widget(high_scores_table(&high_scores, rank), popup);
+            } else if game_over.is_some() {
+                let popup = centered(game_area, 30, 3);
+                let popup = Rect {
+                    y: game_area
+                        .bottom()
+                        .saturating_sub(popup.height + 2)
+                        .max(game_area.y),
+                    ..popup
+                };
+                frame.render_widget(Clear, popup);
+                frame.render_widget(
+                    Paragraph::new(format!(
+                        "Score: {}   Best: {}",
+                        game.score(),
+                        high_scores.best()
+                    ))
+                    .centered()
+                    .bold()
+                    .block(Block::bordered()),
+                    popup,
+                );
+            }
+            if confirm_quit {
+                let popup = centered(game_area, 20, 3);
+                frame.render_widget(Clear, popup);
+                frame.render_widget(
+                    Paragraph::new(format!("Quit? {}/N", key_name(keymap.confirm)))
+                        .centered()
+                        .bold()
+                        .block(Block::bordered()),
+                    popup,
+                );
+            }
         })?;
+        fps.frame(Instant::now());
+
+        if export {
+            export = false;
+            let message = match std::fs::write(&opts.export_path, export::to_ansi(completed.buffer))
+            {
+                Ok(()) => format!("Saved the frame to {}", opts.export_path.display()),
+                Err(e) => format!("Failed to save the frame: {e}"),
+            };
+            status = Some((message, Instant::now()));
+        }
     }
 
+    if let Some(trace) = trace.as_mut() {
+        trace.flush()?;
+    }
+    if !opts.no_mouse {
+        stdout().execute(DisableMouseCapture)?;
+    }
     stdout().execute(LeaveAlternateScreen)?;
     disable_raw_mode()?;
+    if let Some(day) = daily {
+        println!("Daily challenge {day}: seed {}", day.seed());
+    }
     Ok(())
 }
 
+/// Measures the rate the frames are rendered at.
+struct FpsMeter {
+    /// The frames rendered since the rate was last measured.
+    frames: u32,
+    /// When the rate was last measured.
+    since: Instant,
+    /// The last measured rate, in frames per second.
+    fps: f64,
+}
+
+impl FpsMeter {
+    /// Starts measuring the rate from `now`.
+    fn new(now: Instant) -> Self {
+        Self {
+            frames: 0,
+            since: now,
+            fps: 0.,
+        }
+    }
+
+    /// Counts a frame rendered at `now`, measuring the rate again every [`FPS_INTERVAL`].
+    fn frame(&mut self, now: Instant) {
+        self.frames += 1;
+        let elapsed = now.duration_since(self.since);
+        if elapsed >= FPS_INTERVAL {
+            self.fps = f64::from(self.frames) / elapsed.as_secs_f64();
+            self.frames = 0;
+            self.since = now;
+        }
+    }
+
+    /// Returns the last measured rate, in frames per second.
+    fn fps(&self) -> f64 {
+        self.fps
+    }
+}
+
+/// Builds the high-score table with the entry at `rank` highlighted.
+fn high_scores_table(high_scores: &HighScores, rank: usize) -> Paragraph<'static> {
+    let mut lines = vec![
+        Line::from(""),
+        Line::from("Top scores:").centered(),
+        Line::from(""),
+    ];
+    for (i, score) in high_scores.entries().iter().enumerate() {
+        let line = Line::from(format!("{:>2}. {score:>6}", i + 1)).centered();
+        lines.push(if i == rank {
+            line.bold().light_yellow()
+        } else {
+            line
+        });
+    }
+    lines.push(Line::from(""));
+    Paragraph::new(lines).block(
+        Block::bordered()
+            .title(format!(" New high score — rank {}! ", rank + 1))
+            .title_bottom(" ↵ to continue ")
+            .bold(),
+    )
+}
+
+/// Builds the debug side panel listing the most recent simulation effects, the newest at
+/// the bottom.
+#[cfg(feature = "debug")]
+fn effects_panel(game: &Game, area: Rect) -> Paragraph<'static> {
+    let lines: Vec<Line> = game
+        .recent_events()
+        .map(|effect| Line::from(effect.to_string()))
+        .collect();
+    let scroll = lines
+        .len()
+        .saturating_sub(area.height.saturating_sub(2) as usize);
+    Paragraph::new(lines)
+        .scroll((scroll as u16, 0))
+        .block(Block::bordered().title(" Effects "))
+}
+
+/// Returns the marker following `marker` in the order they're cycled through at runtime.
+fn next_marker(marker: Marker) -> Marker {
+    match marker {
+        Marker::Dot => Marker::Braille,
+        Marker::Braille => Marker::Bar,
+        Marker::Bar => Marker::Block,
+        Marker::Block => Marker::HalfBlock,
+        Marker::HalfBlock => Marker::Dot,
+    }
+}
+
+/// Returns a scaled-down overview of the bricks and the ball, the bricks hidden in the fog
+/// left out.
+fn minimap(game: &Game) -> impl Widget + '_ {
+    let area = game.area();
+    Canvas::default()
+        .block(Block::bordered().title(" Map "))
+        .marker(Marker::Braille)
+        .x_bounds([area.left(), area.right()])
+        .y_bounds([area.bottom(), area.top()])
+        .paint(move |ctx| {
+            let bricks: Vec<_> = game
+                .bricks()
+                .iter()
+                .filter(|brick| !brick.is_hidden())
+                .map(|brick| {
+                    let area = brick.area();
+                    (area.x + area.width / 2., area.y + area.height / 2.)
+                })
+                .collect();
+            ctx.draw(&Points {
+                coords: &bricks,
+                color: Color::LightYellow,
+            });
+            let balls: Vec<_> = game.balls().iter().map(Ball::position).collect();
+            ctx.draw(&Points {
+                coords: &balls,
+                color: Color::LightRed,
+            });
+        })
+}
+
+/// Maps the terminal column the mouse is in onto the x-coordinate of the playfield.
+///
+/// # Parameters
+/// - `column`: The column of the mouse.
+/// - `playfield`: The cells the playfield is drawn in.
+///
+/// # Returns
+/// The x-coordinate at the center of the column, clamped to the playfield, or `None` if the
+/// playfield hasn't been drawn yet.
+fn mouse_x(column: u16, playfield: Rect) -> Option<f64> {
+    if playfield.width == 0 {
+        return None;
+    }
+    let offset = (f64::from(column) - f64::from(playfield.x) + 0.5) / f64::from(playfield.width);
+    Some(offset.clamp(0., 1.) * PLAYFIELD_WIDTH)
+}
+
+/// Returns the largest rectangle with the given proportions centered in `area`.
+///
+/// # Parameters
+/// - `area`: The available area.
+/// - `aspect`: The width to height ratio, in cells, to keep.
+///
+/// # Returns
+/// The area narrowed or shortened to the ratio, as close as whole cells allow.
+fn letterboxed(area: Rect, aspect: f64) -> Rect {
+    let width = (area.height as f64 * aspect).round() as u16;
+    if width <= area.width {
+        centered(area, width, area.height)
+    } else {
+        let height = (area.width as f64 / aspect).round() as u16;
+        centered(area, area.width, height)
+    }
+}
+
+/// Returns a rectangle of the given size centered in `area`.
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// Dims the even rows of `area`, counted from its top, to imitate the scanlines of a CRT.
+fn scanlines(buffer: &mut Buffer, area: Rect) {
+    for y in (area.top()..area.bottom()).step_by(2) {
+        for x in area.left()..area.right() {
+            buffer.get_mut(x, y).modifier.insert(Modifier::DIM);
+        }
+    }
+}
+
+/// Parses a size, e.g. of a brick, given as `WIDTHxHEIGHT`.
+fn parse_size(s: &str) -> Result<(f64, f64), String> {
+    let (width, height) = s
+        .split_once('x')
+        .ok_or_else(|| format!("expected WIDTHxHEIGHT, got `{s}`"))?;
+    let width: f64 = width.trim().parse().map_err(|e| format!("{e}"))?;
+    let height: f64 = height.trim().parse().map_err(|e| format!("{e}"))?;
+    if width <= 0. || height <= 0. {
+        return Err(format!("size must be positive, got `{s}`"));
+    }
+    Ok((width, height))
+}
+
 /// Initialize the tracing subscriber to log to a file
 ///
 /// This function initializes the tracing subscriber to log to a file named `tracing.log` in the
@@ -205,3 +1117,62 @@ fn init_tracing() -> anyhow::Result<WorkerGuard> {
         .init();
     Ok(guard)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mouse_x() {
+        let playfield = Rect::new(10, 2, 90, 30);
+        assert_eq!(mouse_x(10, playfield), Some(2.));
+        assert_eq!(mouse_x(99, playfield), Some(358.));
+        // The columns outside of the playfield stick to its edges.
+        assert_eq!(mouse_x(0, playfield), Some(0.));
+        assert_eq!(mouse_x(200, playfield), Some(PLAYFIELD_WIDTH));
+        assert_eq!(mouse_x(10, Rect::default()), None);
+    }
+
+    #[test]
+    fn test_fps_meter() {
+        let start = Instant::now();
+        let mut meter = FpsMeter::new(start);
+        for i in 1..=100 {
+            meter.frame(start + Duration::from_millis(i * 10));
+        }
+        assert_eq!(meter.fps(), 100.);
+        // The rate is only measured again once the interval is over.
+        meter.frame(start + Duration::from_millis(1500));
+        assert_eq!(meter.fps(), 100.);
+        meter.frame(start + Duration::from_millis(2000));
+        assert_eq!(meter.fps(), 2.);
+    }
+
+    #[test]
+    fn test_letterboxed() {
+        // Too wide: pillarboxed.
+        assert_eq!(
+            letterboxed(Rect::new(0, 0, 200, 40), 4.),
+            Rect::new(20, 0, 160, 40)
+        );
+        // Too tall: letterboxed.
+        assert_eq!(
+            letterboxed(Rect::new(0, 0, 80, 40), 4.),
+            Rect::new(0, 10, 80, 20)
+        );
+        // Already fitting, and offset.
+        assert_eq!(
+            letterboxed(Rect::new(5, 3, 80, 20), 4.),
+            Rect::new(5, 3, 80, 20)
+        );
+        // Rounded to whole cells.
+        assert_eq!(
+            letterboxed(Rect::new(0, 0, 81, 30), 4.),
+            Rect::new(0, 5, 81, 20)
+        );
+        assert_eq!(
+            letterboxed(Rect::new(0, 0, 0, 0), 4.),
+            Rect::new(0, 0, 0, 0)
+        );
+    }
+}
@@ -1,10 +1,46 @@
 use crate::ball::{Ball, EllasticCollision};
 use crate::rectf64::Rectf64;
+use crate::render::GameRenderer;
 use ratatui::style::Color;
+#[cfg(feature = "tui")]
 use ratatui::widgets::canvas::{Painter, Shape};
 
+/// The paddle moves in discrete steps, roughly one per frame at the reference 24 FPS.
+/// Used to turn a step into a horizontal speed when spinning the ball.
+const STEPS_PER_SECOND: f64 = 24.0;
+
+/// The narrowest the paddle can be resized to.
+const MIN_WIDTH: f64 = 4.0;
+
+/// How long, in seconds, the highlight of a hit takes to fade.
+const TILT_FADE: f64 = 1.0;
+
+/// Hits this far from the paddle's center, as a share of its half-width, count as edge hits.
+const EDGE_ZONE: f64 = 0.9;
+
+/// Default smallest horizontal speed, in units per second, the ball leaves the paddle's edge
+/// with.
+pub const EDGE_KICK: f64 = 12.0;
+
+/// Default height, above its top, at which the paddle already catches the ball.
+pub const CATCH_TOLERANCE: f64 = 1.0;
+
+/// Default angular factor of the paddle's spin.
+pub const ANGULAR_FACTOR: f64 = 1.5;
+
+/// Default mass factor of the paddle's spin.
+pub const MASS_FACTOR: f64 = 0.7;
+
+/// Default friction between the ball and the paddle.
+pub const FRICTION: f64 = 0.3;
+
+/// The steepest angle, in radians from straight up, the ball leaves the edge of the paddle at
+/// when the bounce angle depends on where the ball hits it.
+pub const MAX_BOUNCE_ANGLE: f64 = std::f64::consts::FRAC_PI_3;
+
 /// Represents the direction in which the paddle can move.
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     #[default]
     Left,
@@ -17,6 +53,7 @@ pub enum Direction {
 
 /// Represents the paddle in the game.
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Paddle {
     /// The rectangular area occupied by the paddle.
     area: Rectf64,
@@ -29,6 +66,30 @@ pub struct Paddle {
     /// The maximum x-coordinate the paddle can move to.
     max_x: f64,
     color: Color,
+    /// The share of the paddle around its center that bounces the ball without spin.
+    deadzone: f64,
+    /// How strongly the angle of impact turns the paddle's movement into spin.
+    angular_factor: f64,
+    /// The mass of the paddle relative to the ball.
+    mass_factor: f64,
+    /// The friction between the ball and the paddle.
+    friction: f64,
+    /// The width the paddle was created with, restored once resizing effects end.
+    base_width: f64,
+    /// Whether the paddle highlights where the ball last hit it.
+    tilt: bool,
+    /// The offset of the last hit from the paddle's center and the remaining time, in
+    /// seconds, its highlight is shown for.
+    last_hit: Option<(f64, f64)>,
+    /// The smallest horizontal speed the ball leaves the paddle's edge with.
+    edge_kick: f64,
+    /// Whether the paddle is drawn with rounded ends.
+    rounded: bool,
+    /// The height above its top at which the paddle already catches the ball.
+    catch_tolerance: f64,
+    /// The angle, in radians from straight up, the ball leaves the paddle's edge at, if the
+    /// bounce depends on where the ball hits the paddle rather than on its movement.
+    bounce_angle: Option<f64>,
 }
 
 impl Paddle {
@@ -41,18 +102,149 @@ impl Paddle {
     /// - `vx`: The horizontal velocity of the paddle.
     ///
     /// # Returns
-    /// A new `Paddle` instance with the specified area, minimum and maximum x-coordinates, and velocity.
+    /// A new `Paddle` instance with the specified area, minimum and maximum x-coordinates, and
+    /// velocity.
     pub fn new(area: Rectf64, min_x: f64, max_x: f64, vx: f64, color: Color) -> Self {
         Self {
+            base_width: area.width,
             area,
             min_x,
             max_x,
             dir: Direction::Left,
             vx,
             color,
+            deadzone: 0.,
+            angular_factor: ANGULAR_FACTOR,
+            mass_factor: MASS_FACTOR,
+            friction: FRICTION,
+            tilt: false,
+            last_hit: None,
+            edge_kick: EDGE_KICK,
+            rounded: false,
+            catch_tolerance: CATCH_TOLERANCE,
+            bounce_angle: None,
         }
     }
 
+    /// Makes the paddle highlight where the ball last hit it, fading over a second.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to show the highlight.
+    ///
+    /// # Returns
+    /// The updated `Paddle`.
+    pub fn tilt(mut self, enabled: bool) -> Self {
+        self.tilt = enabled;
+        self
+    }
+
+    /// Sets how far above its top the paddle already catches the ball, so that a fast ball
+    /// seemingly passing through the top edge still counts as caught.
+    ///
+    /// # Parameters
+    /// - `tolerance`: The height above the paddle, `0.0` to catch only the ball touching it.
+    ///
+    /// # Returns
+    /// The updated `Paddle`.
+    pub fn catch_tolerance(mut self, tolerance: f64) -> Self {
+        self.catch_tolerance = tolerance.max(0.);
+        self
+    }
+
+    /// Makes the ball bounce off at an angle depending on where it hits the paddle, like in
+    /// the classic Arkanoid: straight up from the center and steeper towards the edges, at
+    /// the same speed it came in with. The paddle's movement no longer spins the ball.
+    ///
+    /// # Parameters
+    /// - `max_angle`: The angle, in radians from straight up, the ball leaves the edges at,
+    ///   `None` to spin the ball instead.
+    ///
+    /// # Returns
+    /// The updated `Paddle`.
+    pub fn angled(mut self, max_angle: Option<f64>) -> Self {
+        self.bounce_angle = max_angle;
+        self
+    }
+
+    /// Draws the paddle with rounded ends. The paddle still collides as a rectangle.
+    ///
+    /// # Parameters
+    /// - `enabled`: `true` to round the ends.
+    ///
+    /// # Returns
+    /// The updated `Paddle`.
+    pub fn rounded(mut self, enabled: bool) -> Self {
+        self.rounded = enabled;
+        self
+    }
+
+    /// Records a hit of the ball, to be highlighted if the tilt is enabled.
+    ///
+    /// # Parameters
+    /// - `offset`: Where the ball hit the paddle, as returned by [`Paddle::hit_offset`].
+    pub fn hit(&mut self, offset: f64) {
+        if self.tilt {
+            self.last_hit = Some((offset, TILT_FADE));
+        }
+    }
+
+    /// Fades the highlight of the last hit.
+    ///
+    /// # Parameters
+    /// - `dt`: The time delta, in seconds.
+    pub fn fade(&mut self, dt: f64) {
+        self.last_hit = self
+            .last_hit
+            .map(|(offset, remaining)| (offset, remaining - dt))
+            .filter(|(_, remaining)| *remaining > 0.);
+    }
+
+    /// Sets the coefficients of the spin the moving paddle gives to the ball.
+    ///
+    /// The horizontal velocity the ball gains is the product of the paddle's speed and all
+    /// three coefficients.
+    ///
+    /// # Parameters
+    /// - `angular_factor`: How strongly the angle of impact turns the paddle's movement into spin.
+    /// - `mass_factor`: The mass of the paddle relative to the ball.
+    /// - `friction`: The friction between the ball and the paddle.
+    ///
+    /// # Returns
+    /// The updated `Paddle`.
+    pub fn spin(mut self, angular_factor: f64, mass_factor: f64, friction: f64) -> Self {
+        self.angular_factor = angular_factor;
+        self.mass_factor = mass_factor;
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the central dead zone of the paddle. The ball hitting the dead zone bounces
+    /// back vertically, without any spin from the paddle's movement.
+    ///
+    /// # Parameters
+    /// - `deadzone`: The half-width of the dead zone as a share of the paddle's half-width,
+    ///   in `[0, 1]`.
+    ///
+    /// # Returns
+    /// The updated `Paddle`.
+    pub fn deadzone(mut self, deadzone: f64) -> Self {
+        self.deadzone = deadzone;
+        self
+    }
+
+    /// Sets the smallest horizontal speed the ball leaves the paddle's edge with. A slow ball
+    /// landing on the edge is pushed outwards, so it doesn't hug the edge.
+    ///
+    /// # Parameters
+    /// - `vx`: The speed in units per second, `0.0` to leave the ball as it is.
+    ///
+    /// # Returns
+    /// The updated `Paddle`.
+    pub fn edge_kick(mut self, vx: f64) -> Self {
+        self.edge_kick = vx;
+        self
+    }
+
     /// Moves the paddle in the specified direction.
     ///
     /// # Parameters
@@ -77,13 +269,69 @@ impl Paddle {
         }
         self.dir = direction;
     }
+
+    /// Moves the paddle so that its center is at the given x-coordinate, keeping it between
+    /// `min_x` and `max_x`.
+    ///
+    /// # Parameters
+    /// - `x`: The x-coordinate to center the paddle at.
+    pub fn mov_to(&mut self, x: f64) {
+        let left = (x - self.area.width / 2.).clamp(self.min_x, self.max_x - self.area.width);
+        if left < self.area.x {
+            self.dir = Direction::Left;
+        } else if left > self.area.x {
+            self.dir = Direction::Right;
+        }
+        self.area.x = left;
+    }
+
+    /// Resizes the paddle around its current center.
+    ///
+    /// The width is kept between a small minimum and the space between `min_x` and `max_x`,
+    /// and a paddle that would overflow an edge is shifted inwards.
+    ///
+    /// # Parameters
+    /// - `width`: The new width of the paddle.
+    pub fn set_width(&mut self, width: f64) {
+        let center = self.center();
+        self.area.width = width.clamp(MIN_WIDTH, (self.max_x - self.min_x).max(MIN_WIDTH));
+        self.area.x = (center - self.area.width / 2.)
+            .clamp(self.min_x, (self.max_x - self.area.width).max(self.min_x));
+    }
+
+    /// Returns the width the paddle was created with.
+    pub fn base_width(&self) -> f64 {
+        self.base_width
+    }
+
+    /// Returns the distance the paddle moves by with every [`Paddle::mov`].
+    pub fn step(&self) -> f64 {
+        self.vx
+    }
+
+    /// Returns the x-coordinate of the paddle's center.
+    pub fn center(&self) -> f64 {
+        self.area.x + self.area.width / 2.
+    }
+
+    /// Returns where along the paddle the given x-coordinate lies.
+    ///
+    /// # Parameters
+    /// - `x`: The x-coordinate to locate, usually the ball's center.
+    ///
+    /// # Returns
+    /// The offset from the paddle's center normalized to `[-1, 1]`, where `-1` is the left
+    /// edge and `1` is the right edge.
+    pub fn hit_offset(&self, x: f64) -> f64 {
+        ((x - self.center()) / (self.area.width / 2.)).clamp(-1., 1.)
+    }
 }
 
 impl EllasticCollision for Paddle {
     /// Checks for and handles a collision with the given `Ball`.
     ///
-    /// If the ball intersects with the paddle, the ball's velocity is modified and its
-    /// vertical velocity is reversed.
+    /// If the ball intersects with the paddle, the ball's velocity is modified and it's
+    /// reflected upwards at full speed, whatever the restitution.
     ///
     /// # Parameters
     /// - `ball`: The ball to check for collision.
@@ -91,29 +339,270 @@ impl EllasticCollision for Paddle {
     /// # Returns
     /// `true` if a collision occurred, `false` otherwise.
     fn collide(&self, ball: &mut Ball) {
+        if let Some(max_angle) = self.bounce_angle {
+            ball.aim(self.hit_offset(ball.position().0) * max_angle);
+            return;
+        }
+        if self.hit_offset(ball.position().0).abs() < self.deadzone {
+            ball.rebound((0., 1.));
+            return;
+        }
+
         // Angular factor * mass factor * pad horizontal speed * friction
-        // https://stackoverflow.com/questions/8063696/arkanoid-physics-projectile-physics-simulation
+        // https://stackoverflow.com/q/8063696
         let vx = match self.dir {
             Direction::Left => -1.,
             Direction::Right => 1.,
             #[cfg(feature = "debug")]
             _ => unreachable!(),
-        } * self.vx;
-        ball.dvx(1.5 * 0.7 * vx * 0.3);
-        ball.bouncev();
+        } * self.vx
+            * STEPS_PER_SECOND;
+        ball.dvx(self.angular_factor * self.mass_factor * vx * self.friction);
+        ball.rebound((0., 1.));
+
+        // A slow ball on the edge would hug it, pushed around by the paddle, so send it off
+        // outwards.
+        let offset = self.hit_offset(ball.position().0);
+        let (vx, _) = ball.velocity();
+        if offset.abs() >= EDGE_ZONE && vx.abs() < self.edge_kick {
+            ball.dvx(offset.signum() * self.edge_kick - vx);
+        }
     }
 
     fn area(&self) -> Rectf64 {
         self.area.clone()
     }
+
+    fn collision_area(&self) -> Rectf64 {
+        Rectf64 {
+            height: self.area.height + self.catch_tolerance,
+            ..self.area.clone()
+        }
+    }
+
+    /// A ball already moving up, e.g. leaving the paddle through the catch tolerance above
+    /// it, passes through rather than being bounced back down.
+    fn lets_through(&self, ball: &Ball) -> bool {
+        ball.velocity().1 >= 0.
+    }
+}
+
+impl Paddle {
+    /// Draws the paddle with the given renderer.
+    ///
+    /// # Parameters
+    /// - `renderer`: The renderer to draw the paddle with.
+    pub fn render<R: GameRenderer>(&self, renderer: &mut R) {
+        let mut body = self.area.clone();
+        if self.rounded {
+            // Cap the ends, as wide as they're tall, with filled half circles.
+            let radius = (self.area.height / 2.).min(self.area.width / 2.);
+            body.x += radius;
+            body.width -= 2. * radius;
+            for x in [self.area.left() + radius, self.area.right() - radius] {
+                for k in (1..=4).map(|d| 1. / d as f64) {
+                    renderer.draw_circle(x, self.area.y + radius, radius * k, self.color);
+                }
+            }
+        }
+        let Some((offset, remaining)) = self.last_hit else {
+            renderer.fill_rect(&body, self.color);
+            return;
+        };
+        // The highlight around the hit narrows as it fades.
+        let reach = 0.5 * remaining / TILT_FADE;
+        for (x, y) in body.grid_points() {
+            let color = if (self.hit_offset(x + 0.5) - offset).abs() < reach {
+                Color::White
+            } else {
+                self.color
+            };
+            renderer.draw_point(x, y, color);
+        }
+    }
 }
 
+#[cfg(feature = "tui")]
 impl Shape for Paddle {
     /// Draws the paddle on the given `Painter`.
     ///
     /// # Parameters
     /// - `painter`: The painter to draw the paddle on.
     fn draw(&self, painter: &mut Painter) {
-        self.area.draw(painter, self.color);
+        self.render(painter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paddle() -> Paddle {
+        let area = Rectf64 {
+            x: 40.,
+            y: 2.,
+            width: 20.,
+            height: 2.,
+        };
+        Paddle::new(area, 0., 100., 8., Color::default())
+    }
+
+    #[test]
+    fn test_deadzone() {
+        let paddle = paddle().deadzone(0.2);
+
+        let mut ball = Ball::new(50.5, 5., 3., 10., -20.);
+        paddle.collide(&mut ball);
+        assert_eq!(ball.velocity(), (10., 20.));
+
+        // Outside of the dead zone the paddle spins the ball.
+        let mut ball = Ball::new(55., 5., 3., 10., -20.);
+        paddle.collide(&mut ball);
+        let (vx, vy) = ball.velocity();
+        assert!(vx < 10.);
+        assert_eq!(vy, 20.);
+    }
+
+    #[test]
+    fn test_angled_bounce() {
+        let paddle = paddle().angled(Some(MAX_BOUNCE_ANGLE));
+        let speed = 30f64.hypot(-40.);
+
+        // From the center the ball goes straight up, whatever it came in with.
+        let mut ball = Ball::new(50., 5., 3., 30., -40.);
+        paddle.collide(&mut ball);
+        let (vx, vy) = ball.velocity();
+        assert!(vx.abs() < 1e-9 && (vy - speed).abs() < 1e-9);
+
+        // Towards the edges it leaves steeper, at the same speed.
+        for (x, angle) in [(60., MAX_BOUNCE_ANGLE), (45., -MAX_BOUNCE_ANGLE / 2.)] {
+            let mut ball = Ball::new(x, 5., 3., 30., -40.);
+            paddle.collide(&mut ball);
+            let (vx, vy) = ball.velocity();
+            assert!((vx.atan2(vy) - angle).abs() < 1e-9);
+            assert!((vx.hypot(vy) - speed).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_mov_to() {
+        let mut paddle = paddle();
+
+        paddle.mov_to(-30.);
+        assert_eq!(paddle.area().x, 0.);
+        assert_eq!(paddle.center(), 10.);
+        assert!(matches!(paddle.dir, Direction::Left));
+
+        paddle.mov_to(130.);
+        assert_eq!(paddle.area().x, 80.);
+        assert_eq!(paddle.center(), 90.);
+        assert!(matches!(paddle.dir, Direction::Right));
+
+        paddle.mov_to(35.);
+        assert_eq!(paddle.area().x, 25.);
+        assert_eq!(paddle.center(), 35.);
+        assert!(matches!(paddle.dir, Direction::Left));
+
+        // Staying put keeps the last direction for the spin.
+        paddle.mov_to(35.);
+        assert!(matches!(paddle.dir, Direction::Left));
+    }
+
+    #[test]
+    fn test_set_width_near_the_edge_shifts_inwards() {
+        let mut paddle = paddle();
+        paddle.mov_to(85.);
+
+        paddle.set_width(40.);
+        assert_eq!(paddle.area().width, 40.);
+        assert_eq!(paddle.area().x, 60.);
+        assert_eq!(paddle.area().right(), 100.);
+
+        paddle.set_width(paddle.base_width());
+        assert_eq!(paddle.area().width, 20.);
+        assert_eq!(paddle.center(), 80.);
+    }
+
+    #[test]
+    fn test_set_width_is_clamped() {
+        let mut paddle = paddle();
+
+        paddle.set_width(1.);
+        assert_eq!(paddle.area().width, MIN_WIDTH);
+        assert_eq!(paddle.center(), 50.);
+
+        paddle.set_width(500.);
+        assert_eq!(paddle.area().width, 100.);
+        assert_eq!(paddle.area().x, 0.);
+    }
+
+    #[test]
+    fn test_hit_highlight_fades() {
+        let mut paddle = paddle();
+        paddle.hit(0.5);
+        assert_eq!(paddle.last_hit, None);
+
+        let mut paddle = paddle.tilt(true);
+        paddle.hit(0.5);
+        paddle.fade(TILT_FADE / 2.);
+        assert_eq!(paddle.last_hit, Some((0.5, TILT_FADE / 2.)));
+        paddle.fade(TILT_FADE / 2.);
+        assert_eq!(paddle.last_hit, None);
+    }
+
+    #[test]
+    fn test_catch_tolerance() {
+        // The ball is half a unit above the paddle's top.
+        let ball = || Ball::new(50., 7.5, 3., 0., -20.);
+        assert!(ball().collision(&paddle()));
+        assert_eq!(ball().velocity().1, -20.);
+        let mut caught = ball();
+        caught.collision(&paddle());
+        assert_eq!(caught.velocity().1, 20.);
+
+        assert!(!ball().collision(&paddle().catch_tolerance(0.)));
+        assert!(!Ball::new(50., 8.5, 3., 0., -20.).collision(&paddle()));
+
+        // A ball leaving the paddle through the tolerance isn't bounced back down.
+        let mut rising = Ball::new(50., 7.5, 3., 0., 20.);
+        assert!(!rising.collision(&paddle()));
+        assert_eq!(rising.velocity().1, 20.);
+    }
+
+    #[test]
+    fn test_slow_ball_leaves_the_edge() {
+        let paddle = paddle().spin(ANGULAR_FACTOR, MASS_FACTOR, 0.);
+        for (x, direction) in [(59.5, 1.), (40.5, -1.)] {
+            let mut ball = Ball::new(x, 5., 3., 0.5, -20.);
+            paddle.collide(&mut ball);
+            let (vx, vy) = ball.velocity();
+            assert_eq!(vx, direction * EDGE_KICK);
+            assert_eq!(vy, 20.);
+        }
+
+        // A fast ball keeps its speed, and so does one off the edge.
+        let mut ball = Ball::new(59.5, 5., 3., -30., -20.);
+        paddle.collide(&mut ball);
+        assert_eq!(ball.velocity().0, -30.);
+        let mut ball = Ball::new(55., 5., 3., 0.5, -20.);
+        paddle.collide(&mut ball);
+        assert_eq!(ball.velocity().0, 0.5);
+
+        let paddle = paddle.edge_kick(0.);
+        let mut ball = Ball::new(59.5, 5., 3., 0.5, -20.);
+        paddle.collide(&mut ball);
+        assert_eq!(ball.velocity().0, 0.5);
+    }
+
+    #[test]
+    fn test_friction_increases_spin() {
+        let spin = |friction| {
+            let paddle = paddle().spin(ANGULAR_FACTOR, MASS_FACTOR, friction);
+            let mut ball = Ball::new(55., 5., 3., 0., -20.);
+            paddle.collide(&mut ball);
+            ball.velocity().0.abs()
+        };
+        assert!(spin(0.6) > spin(FRICTION));
+        assert_eq!(spin(0.), 0.);
     }
 }
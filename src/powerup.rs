@@ -0,0 +1,133 @@
+use crate::color::ColorMode;
+use crate::letters::Letter;
+use crate::rectf64::Rectf64;
+use crate::render::GameRenderer;
+use ratatui::style::Color;
+
+/// Width of a falling power-up.
+const WIDTH: f64 = 9.0;
+
+/// Height of a falling power-up.
+const HEIGHT: f64 = 7.0;
+
+/// The speed, in units per second, power-ups fall with.
+pub const FALL_SPEED: f64 = 20.0;
+
+/// The effect of a power-up caught with the paddle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PowerUpKind {
+    /// Widens the paddle for a while.
+    WidePaddle,
+    /// Narrows the paddle for a while, a catch better avoided.
+    NarrowPaddle,
+    /// Slows the ball down.
+    SlowBall,
+    /// Adds a life.
+    ExtraLife,
+    /// Splits every ball in play in two.
+    MultiBall,
+}
+
+impl PowerUpKind {
+    /// Every kind of power-up, the ones dropped by bricks are picked from.
+    pub const ALL: [PowerUpKind; 5] = [
+        PowerUpKind::WidePaddle,
+        PowerUpKind::NarrowPaddle,
+        PowerUpKind::SlowBall,
+        PowerUpKind::ExtraLife,
+        PowerUpKind::MultiBall,
+    ];
+
+    /// Returns the letter and the color the power-up is drawn with.
+    fn look(self) -> (char, Color) {
+        match self {
+            PowerUpKind::WidePaddle => ('w', Color::LightBlue),
+            PowerUpKind::NarrowPaddle => ('n', Color::LightMagenta),
+            PowerUpKind::SlowBall => ('s', Color::LightGreen),
+            PowerUpKind::ExtraLife => ('l', Color::LightRed),
+            PowerUpKind::MultiBall => ('m', Color::White),
+        }
+    }
+}
+
+/// A power-up dropped by a destroyed brick, falling towards the paddle.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerUp {
+    /// The rectangular area occupied by the power-up.
+    area: Rectf64,
+    /// The speed, in units per second, the power-up falls with.
+    vy: f64,
+    /// The effect of the power-up.
+    kind: PowerUpKind,
+}
+
+impl PowerUp {
+    /// Creates a new `PowerUp` instance.
+    ///
+    /// # Parameters
+    /// - `kind`: The effect of the power-up.
+    /// - `center`: The `(x, y)` coordinates the power-up is centered at, usually the center
+    ///   of the destroyed brick.
+    ///
+    /// # Returns
+    /// A new `PowerUp` falling with [`FALL_SPEED`].
+    pub fn new(kind: PowerUpKind, center: (f64, f64)) -> Self {
+        Self {
+            area: Rectf64 {
+                x: center.0 - WIDTH / 2.,
+                y: center.1 - HEIGHT / 2.,
+                width: WIDTH,
+                height: HEIGHT,
+            },
+            vy: FALL_SPEED,
+            kind,
+        }
+    }
+
+    /// Returns the effect of the power-up.
+    pub fn kind(&self) -> PowerUpKind {
+        self.kind
+    }
+
+    /// Returns the rectangular area occupied by the power-up.
+    pub fn area(&self) -> &Rectf64 {
+        &self.area
+    }
+
+    /// Moves the power-up down.
+    ///
+    /// # Parameters
+    /// - `dt`: The time delta, in seconds.
+    pub fn fall(&mut self, dt: f64) {
+        self.area.y -= self.vy * dt;
+    }
+
+    /// Draws the power-up with the given renderer in the given color mode.
+    ///
+    /// # Parameters
+    /// - `renderer`: The renderer to draw the power-up with.
+    /// - `mode`: The color mode to draw in.
+    pub fn draw_in<R: GameRenderer>(&self, renderer: &mut R, mode: ColorMode) {
+        let (letter, color) = self.kind.look();
+        let color = mode.apply(color);
+        renderer.draw_rect(&self.area, color);
+        // The letters are 5 units tall and at most 4 wide.
+        Letter::new_letter(letter, (self.area.x + 2.5, self.area.y + 1.), color).render(renderer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fall() {
+        let mut powerup = PowerUp::new(PowerUpKind::ExtraLife, (50., 30.));
+        assert_eq!(powerup.area().x, 50. - WIDTH / 2.);
+        powerup.fall(0.5);
+        assert_eq!(powerup.area().y, 30. - HEIGHT / 2. - FALL_SPEED / 2.);
+        assert_eq!(powerup.kind(), PowerUpKind::ExtraLife);
+    }
+}
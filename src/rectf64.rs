@@ -1,10 +1,9 @@
 use ratatui::prelude::Rect;
-use ratatui::style::Color;
-use ratatui::widgets::canvas::{Painter, Points, Shape};
 
 /// Represents a rectangle with floating-point coordinates and dimensions.
 #[derive(Debug, Default, PartialOrd, PartialEq, Clone)]
-pub(crate) struct Rectf64 {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rectf64 {
     /// The x-coordinate of the rectangle's origin.
     pub x: f64,
     /// The y-coordinate of the rectangle's origin.
@@ -38,7 +37,7 @@ impl Rectf64 {
     ///
     /// # Returns
     /// The x-coordinate of the left edge.
-    pub(crate) fn left(&self) -> f64 {
+    pub fn left(&self) -> f64 {
         self.x
     }
 
@@ -46,7 +45,7 @@ impl Rectf64 {
     ///
     /// # Returns
     /// The x-coordinate of the right edge.
-    pub(crate) fn right(&self) -> f64 {
+    pub fn right(&self) -> f64 {
         self.x + self.width
     }
 
@@ -54,7 +53,7 @@ impl Rectf64 {
     ///
     /// # Returns
     /// The y-coordinate of the top edge.
-    pub(crate) fn top(&self) -> f64 {
+    pub fn top(&self) -> f64 {
         self.y + self.height
     }
 
@@ -62,27 +61,47 @@ impl Rectf64 {
     ///
     /// # Returns
     /// The y-coordinate of the bottom edge.
-    pub(crate) fn bottom(&self) -> f64 {
+    pub fn bottom(&self) -> f64 {
         self.y
     }
 
-    /// Draws the rectangle on the given `Painter` using the specified color.
+    /// Checks whether the rectangle fully contains another one.
     ///
     /// # Parameters
-    /// - `painter`: The painter to draw the rectangle on.
-    /// - `color`: The color to use for drawing the rectangle.
-    pub(crate) fn draw(&self, painter: &mut Painter, color: Color) {
-        let mut points = vec![];
-        for x in self.left() as u16..self.right() as u16 {
-            for y in self.bottom() as u16..self.top() as u16 {
-                points.push((x as f64, y as f64));
-            }
-        }
-        Points {
-            coords: &points,
-            color,
-        }
-        .draw(painter);
+    /// - `other`: The rectangle to check.
+    ///
+    /// # Returns
+    /// `true` if `other` lies entirely within the rectangle, `false` otherwise.
+    pub fn contains(&self, other: &Rectf64) -> bool {
+        other.left() >= self.left()
+            && other.right() <= self.right()
+            && other.bottom() >= self.bottom()
+            && other.top() <= self.top()
+    }
+
+    /// Checks whether the rectangle overlaps another one. Touching edges don't count.
+    ///
+    /// # Parameters
+    /// - `other`: The rectangle to check.
+    ///
+    /// # Returns
+    /// `true` if the rectangles overlap, `false` otherwise.
+    pub fn intersects(&self, other: &Rectf64) -> bool {
+        self.left() < other.right()
+            && other.left() < self.right()
+            && self.bottom() < other.top()
+            && other.bottom() < self.top()
+    }
+
+    /// Returns the integer-aligned points covered by the rectangle, column by column.
+    ///
+    /// # Returns
+    /// An iterator over the `(x, y)` points with the edges truncated to integers, the right
+    /// and top edges excluded.
+    pub fn grid_points(&self) -> impl Iterator<Item = (f64, f64)> {
+        let ys = self.bottom() as u16..self.top() as u16;
+        (self.left() as u16..self.right() as u16)
+            .flat_map(move |x| ys.clone().map(move |y| (x as f64, y as f64)))
     }
 }
 
@@ -120,4 +139,49 @@ mod tests {
         assert_eq!(rect.top(), 60.0);
         assert_eq!(rect.bottom(), 20.0);
     }
+
+    #[test]
+    fn test_contains_and_intersects() {
+        let rect = Rectf64 {
+            x: 0.,
+            y: 0.,
+            width: 10.,
+            height: 10.,
+        };
+        let inner = Rectf64 {
+            x: 2.,
+            y: 2.,
+            width: 5.,
+            height: 5.,
+        };
+        let touching = Rectf64 {
+            x: 10.,
+            y: 0.,
+            width: 5.,
+            height: 5.,
+        };
+
+        assert!(rect.contains(&inner));
+        assert!(!inner.contains(&rect));
+        assert!(rect.intersects(&inner));
+        assert!(!rect.intersects(&touching));
+    }
+
+    #[test]
+    fn test_grid_points() {
+        let rect = Rectf64 {
+            x: 1.5,
+            y: 2.,
+            width: 3.,
+            height: 2.,
+        };
+        let points: Vec<_> = rect.grid_points().collect();
+        assert_eq!(
+            points,
+            vec![(1., 2.), (1., 3.), (2., 2.), (2., 3.), (3., 2.), (3., 3.)]
+        );
+        assert!(points
+            .iter()
+            .all(|&(x, y)| x >= 1. && x < rect.right() && y >= rect.bottom() && y < rect.top()));
+    }
 }
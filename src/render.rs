@@ -0,0 +1,215 @@
+use crate::letters::Letter;
+use crate::rectf64::Rectf64;
+use ratatui::style::Color;
+#[cfg(feature = "tui")]
+use ratatui::widgets::canvas::{Circle, Line, Painter, Rectangle, Shape};
+
+/// Draws the primitives the game is made of, decoupling the shapes from the backend they're
+/// drawn with. Implemented for ratatui's
+/// [`Painter`](ratatui::widgets::canvas::Painter), the game's usual canvas, with the `tui`
+/// feature.
+pub trait GameRenderer {
+    /// Paints a single point.
+    ///
+    /// # Parameters
+    /// - `x`, `y`: The coordinates of the point.
+    /// - `color`: The color of the point.
+    fn draw_point(&mut self, x: f64, y: f64, color: Color);
+
+    /// Draws a straight line.
+    ///
+    /// # Parameters
+    /// - `x1`, `y1`: The coordinates of the line's start.
+    /// - `x2`, `y2`: The coordinates of the line's end.
+    /// - `color`: The color of the line.
+    fn draw_line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, color: Color);
+
+    /// Draws the outline of a circle.
+    ///
+    /// # Parameters
+    /// - `x`, `y`: The coordinates of the circle's center.
+    /// - `radius`: The radius of the circle.
+    /// - `color`: The color of the circle.
+    fn draw_circle(&mut self, x: f64, y: f64, radius: f64, color: Color);
+
+    /// Draws the outline of a rectangle.
+    ///
+    /// # Parameters
+    /// - `area`: The rectangle to draw.
+    /// - `color`: The color of the outline.
+    fn draw_rect(&mut self, area: &Rectf64, color: Color) {
+        let (left, right, bottom, top) = (area.left(), area.right(), area.bottom(), area.top());
+        self.draw_line(left, bottom, right, bottom, color);
+        self.draw_line(left, top, right, top, color);
+        self.draw_line(left, bottom, left, top, color);
+        self.draw_line(right, bottom, right, top, color);
+    }
+
+    /// Fills a rectangle, point by point.
+    ///
+    /// # Parameters
+    /// - `area`: The rectangle to fill.
+    /// - `color`: The fill color.
+    fn fill_rect(&mut self, area: &Rectf64, color: Color) {
+        for (x, y) in area.grid_points() {
+            self.draw_point(x, y, color);
+        }
+    }
+
+    /// Draws text with the game's line-drawn letters.
+    ///
+    /// # Parameters
+    /// - `text`: The text to draw, in lowercase.
+    /// - `position`: The bottom-left corner of the first letter.
+    /// - `spacing`: The distance between the starts of consecutive letters.
+    /// - `color`: The color of the text.
+    fn draw_text(&mut self, text: &str, position: (f64, f64), spacing: f64, color: Color) {
+        for (i, letter) in text.chars().enumerate() {
+            Letter::new_letter(letter, (position.0 + i as f64 * spacing, position.1), color)
+                .render(self);
+        }
+    }
+}
+
+#[cfg(feature = "tui")]
+impl GameRenderer for Painter<'_, '_> {
+    fn draw_point(&mut self, x: f64, y: f64, color: Color) {
+        if let Some((x, y)) = self.get_point(x, y) {
+            self.paint(x, y, color);
+        }
+    }
+
+    fn draw_line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, color: Color) {
+        Line {
+            x1,
+            y1,
+            x2,
+            y2,
+            color,
+        }
+        .draw(self);
+    }
+
+    fn draw_circle(&mut self, x: f64, y: f64, radius: f64, color: Color) {
+        Circle {
+            x,
+            y,
+            radius,
+            color,
+        }
+        .draw(self);
+    }
+
+    fn draw_rect(&mut self, area: &Rectf64, color: Color) {
+        Rectangle {
+            x: area.x,
+            y: area.y,
+            width: area.width,
+            height: area.height,
+            color,
+        }
+        .draw(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ball::Ball;
+    use crate::brick::{Brick, BrickStyle};
+    use crate::color::ColorMode;
+    use crate::letters::Word;
+
+    /// A draw call, as recorded by the [`Recorder`].
+    #[derive(Debug, PartialEq)]
+    enum Call {
+        Point,
+        Line,
+        Circle(f64),
+        Rect(Rectf64, Color),
+        Text(String, Color),
+    }
+
+    /// Records the draw calls instead of drawing anything.
+    #[derive(Default)]
+    struct Recorder {
+        calls: Vec<Call>,
+    }
+
+    impl GameRenderer for Recorder {
+        fn draw_point(&mut self, _x: f64, _y: f64, _color: Color) {
+            self.calls.push(Call::Point);
+        }
+
+        fn draw_line(&mut self, _x1: f64, _y1: f64, _x2: f64, _y2: f64, _color: Color) {
+            self.calls.push(Call::Line);
+        }
+
+        fn draw_circle(&mut self, _x: f64, _y: f64, radius: f64, _color: Color) {
+            self.calls.push(Call::Circle(radius));
+        }
+
+        fn draw_rect(&mut self, area: &Rectf64, color: Color) {
+            self.calls.push(Call::Rect(area.clone(), color));
+        }
+
+        fn draw_text(&mut self, text: &str, _position: (f64, f64), _spacing: f64, color: Color) {
+            self.calls.push(Call::Text(text.to_string(), color));
+        }
+    }
+
+    #[test]
+    fn test_recorded_scene() {
+        let mut recorder = Recorder::default();
+        Brick::new(Rectf64 {
+            x: 10.,
+            y: 20.,
+            width: 14.,
+            height: 5.,
+        })
+        .draw_in(&mut recorder, ColorMode::Full, &BrickStyle::default());
+        Ball::new(50., 50., 2., 0., 0.).draw_in(&mut recorder, ColorMode::Full);
+        Word::new("hi".to_string(), (0., 0.), 5., Color::White).render(&mut recorder);
+
+        let mut expected = vec![Call::Rect(
+            Rectf64 {
+                x: 11.,
+                y: 21.,
+                width: 13.,
+                height: 4.,
+            },
+            Color::LightYellow,
+        )];
+        // The ball is filled with shrinking circles.
+        expected.extend((1..=10).map(|d| Call::Circle(2. / d as f64)));
+        expected.push(Call::Text("hi".to_string(), Color::White));
+        assert_eq!(recorder.calls, expected);
+    }
+
+    #[test]
+    fn test_text_is_drawn_with_lines() {
+        struct Lines(usize);
+        impl GameRenderer for Lines {
+            fn draw_point(&mut self, _x: f64, _y: f64, _color: Color) {}
+            fn draw_line(&mut self, _x1: f64, _y1: f64, _x2: f64, _y2: f64, _color: Color) {
+                self.0 += 1;
+            }
+            fn draw_circle(&mut self, _x: f64, _y: f64, _radius: f64, _color: Color) {}
+        }
+
+        let mut lines = Lines(0);
+        lines.draw_text("a", (0., 0.), 5., Color::White);
+        // `a` is made of three strokes.
+        assert_eq!(lines.0, 3);
+        lines.fill_rect(
+            &Rectf64 {
+                x: 0.,
+                y: 0.,
+                width: 2.,
+                height: 2.,
+            },
+            Color::White,
+        );
+        assert_eq!(lines.0, 3);
+    }
+}
@@ -0,0 +1,146 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// The number of entries kept in the high-score table.
+pub const MAX_ENTRIES: usize = 10;
+
+/// A table of the best scores, highest first.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScores {
+    /// The scores in the table, sorted from the highest to the lowest.
+    entries: Vec<usize>,
+}
+
+impl HighScores {
+    /// Returns the scores in the table, highest first.
+    pub fn entries(&self) -> &[usize] {
+        &self.entries
+    }
+
+    /// Returns the best score in the table, `0` if it's empty.
+    pub fn best(&self) -> usize {
+        self.entries.first().copied().unwrap_or_default()
+    }
+
+    /// Inserts a score into the table, dropping scores that fall out of the top
+    /// [`MAX_ENTRIES`]. Equal scores rank below the ones already in the table.
+    ///
+    /// # Parameters
+    /// - `score`: The score to insert.
+    ///
+    /// # Returns
+    /// The zero-based rank of the score if it made the table, `None` otherwise.
+    pub fn insert(&mut self, score: usize) -> Option<usize> {
+        if score == 0 {
+            return None;
+        }
+        let rank = self.entries.partition_point(|&entry| entry >= score);
+        if rank >= MAX_ENTRIES {
+            return None;
+        }
+        self.entries.insert(rank, score);
+        self.entries.truncate(MAX_ENTRIES);
+        Some(rank)
+    }
+}
+
+/// Returns the file the high scores are kept in, `~/.arkanoid-tui/scores.json`.
+///
+/// # Returns
+/// The path, or `None` if the home directory is unknown.
+pub fn default_path() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME").filter(|home| !home.is_empty())?;
+    Some(
+        PathBuf::from(home)
+            .join(".arkanoid-tui")
+            .join("scores.json"),
+    )
+}
+
+/// Reads the high-score table from a file.
+///
+/// # Parameters
+/// - `path`: The file to read.
+///
+/// # Returns
+/// The table read, or an empty one if the file is missing or corrupt.
+pub fn load(path: &Path) -> HighScores {
+    let Some(mut high_scores) = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str::<HighScores>(&json).ok())
+    else {
+        return HighScores::default();
+    };
+    // Don't trust a file edited by hand to be sorted and short enough.
+    high_scores.entries.sort_by(|a, b| b.cmp(a));
+    high_scores.entries.retain(|&score| score > 0);
+    high_scores.entries.truncate(MAX_ENTRIES);
+    high_scores
+}
+
+/// Writes the high-score table to a file, creating its directory if needed.
+///
+/// # Parameters
+/// - `path`: The file to write.
+/// - `high_scores`: The table to write.
+///
+/// # Returns
+/// An error if the file couldn't be written.
+pub fn save(path: &Path, high_scores: &HighScores) -> anyhow::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    let json = serde_json::to_string_pretty(high_scores)?;
+    std::fs::write(path, json).with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_rank() {
+        let mut scores = HighScores::default();
+        assert_eq!(scores.insert(10), Some(0));
+        assert_eq!(scores.insert(30), Some(0));
+        assert_eq!(scores.insert(20), Some(1));
+        assert_eq!(scores.insert(20), Some(2));
+        assert_eq!(scores.insert(0), None);
+        assert_eq!(scores.entries(), &[30, 20, 20, 10]);
+    }
+
+    #[test]
+    fn test_insert_truncates() {
+        let mut scores = HighScores::default();
+        for score in 1..=MAX_ENTRIES + 5 {
+            scores.insert(score);
+        }
+        assert_eq!(scores.entries().len(), MAX_ENTRIES);
+        assert_eq!(scores.entries()[0], MAX_ENTRIES + 5);
+        assert_eq!(scores.insert(1), None);
+        assert_eq!(scores.insert(100), Some(0));
+        assert_eq!(scores.entries().len(), MAX_ENTRIES);
+    }
+
+    #[test]
+    fn test_load_save() {
+        let dir = std::env::temp_dir().join(format!("arkanoid-scores-{}", std::process::id()));
+        let path = dir.join("nested").join("scores.json");
+        assert_eq!(load(&path), HighScores::default());
+
+        let mut scores = HighScores::default();
+        scores.insert(30);
+        scores.insert(10);
+        save(&path, &scores).unwrap();
+        assert_eq!(load(&path), scores);
+        assert_eq!(load(&path).best(), 30);
+
+        // A corrupt file starts the table from scratch.
+        std::fs::write(&path, "{not json").unwrap();
+        assert_eq!(load(&path), HighScores::default());
+        assert_eq!(load(&path).best(), 0);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
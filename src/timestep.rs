@@ -0,0 +1,120 @@
+//! A fixed timestep for the simulation, so that the game plays the same at any frame rate.
+
+/// Default number of simulation steps per second.
+pub const STEP_RATE: u16 = 120;
+
+/// Default upper bound for the number of steps taken at once to catch up with the elapsed
+/// time, e.g. after the terminal was suspended.
+pub const MAX_CATCH_UP_STEPS: u32 = 12;
+
+/// Turns the elapsed real time into a whole number of fixed simulation steps, carrying the
+/// remainder over to the next frame.
+#[derive(Debug, Clone)]
+pub struct FixedTimestep {
+    /// The duration, in seconds, of a single step.
+    step: f64,
+    /// The most steps taken at once.
+    max_steps: u32,
+    /// The elapsed time, in seconds, not simulated yet.
+    accumulated: f64,
+    /// Whether the time is frozen, e.g. while the game is paused.
+    paused: bool,
+}
+
+impl FixedTimestep {
+    /// Creates a new `FixedTimestep`.
+    ///
+    /// # Parameters
+    /// - `rate`: The number of steps per second, at least one.
+    /// - `max_steps`: The most steps taken at once, at least one.
+    ///
+    /// # Returns
+    /// A new `FixedTimestep` with no time accumulated.
+    pub fn new(rate: u16, max_steps: u32) -> Self {
+        Self {
+            step: 1. / f64::from(rate.max(1)),
+            max_steps: max_steps.max(1),
+            accumulated: 0.,
+            paused: false,
+        }
+    }
+
+    /// Returns the duration, in seconds, of a single step.
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// Freezes or unfreezes the time. While frozen the elapsed time is ignored, and the
+    /// partial step accumulated before is kept for when the time goes on.
+    ///
+    /// # Parameters
+    /// - `paused`: `true` to freeze the time.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Returns whether the time is frozen.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Accumulates the elapsed time and takes the steps it's worth. Time beyond
+    /// `max_steps` steps is dropped, so that a long stall doesn't snowball into ever longer
+    /// catch-ups.
+    ///
+    /// # Parameters
+    /// - `elapsed`: The time, in seconds, elapsed since the last call.
+    ///
+    /// # Returns
+    /// The number of steps to simulate, none while the time is frozen.
+    pub fn advance(&mut self, elapsed: f64) -> u32 {
+        if self.paused {
+            return 0;
+        }
+        self.accumulated += elapsed.max(0.);
+        let steps = (self.accumulated / self.step).floor();
+        if steps >= f64::from(self.max_steps) {
+            self.accumulated = 0.;
+            return self.max_steps;
+        }
+        self.accumulated -= steps * self.step;
+        steps as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remainder_is_carried_over() {
+        let mut timestep = FixedTimestep::new(100, MAX_CATCH_UP_STEPS);
+        assert_eq!(timestep.advance(0.025), 2);
+        assert_eq!(timestep.advance(0.025), 3);
+        assert_eq!(timestep.advance(0.004), 0);
+        // At any frame rate the same time is simulated.
+        let mut slow = FixedTimestep::new(100, MAX_CATCH_UP_STEPS);
+        let mut fast = FixedTimestep::new(100, MAX_CATCH_UP_STEPS);
+        let slow_steps: u32 = (0..10).map(|_| slow.advance(0.1)).sum();
+        let fast_steps: u32 = (0..50).map(|_| fast.advance(0.02)).sum();
+        assert_eq!(slow_steps, fast_steps);
+    }
+
+    #[test]
+    fn test_pause_freezes_the_remainder() {
+        let mut timestep = FixedTimestep::new(64, MAX_CATCH_UP_STEPS);
+        assert_eq!(timestep.advance(1.5 / 64.), 1);
+        timestep.set_paused(true);
+        assert_eq!(timestep.advance(1.), 0);
+        timestep.set_paused(false);
+        assert_eq!(timestep.advance(0.5 / 64.), 1);
+    }
+
+    #[test]
+    fn test_catch_up_is_capped() {
+        let mut timestep = FixedTimestep::new(STEP_RATE, MAX_CATCH_UP_STEPS);
+        assert_eq!(timestep.advance(5.), MAX_CATCH_UP_STEPS);
+        // The stall is forgotten rather than caught up with later.
+        assert_eq!(timestep.advance(0.), 0);
+    }
+}
@@ -0,0 +1,118 @@
+//! Deterministic traces of a game, used to catch physics regressions with golden files.
+//!
+//! A trace is plain text. It starts with the [`HEADER`] line, followed by one line per tick:
+//!
+//! ```text
+//! TICK BALL_X BALL_Y SCORE STATE
+//! ```
+//!
+//! where `TICK` counts from `1`, the ball's coordinates have three decimals and `STATE` is
+//! the [`GameState`] name, e.g. `0001 182.000 8.600 0 Running`. Any change to the format must
+//! bump the version in the header so that golden files are updated intentionally.
+
+use crate::game::{Game, GameState};
+use std::io::{self, Write};
+
+/// The first line of every trace, naming the format version and the columns.
+pub const HEADER: &str = "# arkanoid-trace v1: tick ball_x ball_y score state";
+
+/// Writes the trace of a game, one line per tick.
+#[derive(Debug)]
+pub struct TraceWriter<W: Write> {
+    /// Where the trace is written to.
+    out: W,
+    /// The number of ticks recorded so far.
+    tick: u64,
+}
+
+impl<W: Write> TraceWriter<W> {
+    /// Creates a new `TraceWriter`, writing the header right away.
+    ///
+    /// # Parameters
+    /// - `out`: Where to write the trace to.
+    ///
+    /// # Returns
+    /// The new `TraceWriter`, or the error of writing the header.
+    pub fn new(mut out: W) -> io::Result<Self> {
+        writeln!(out, "{HEADER}")?;
+        Ok(Self { out, tick: 0 })
+    }
+
+    /// Records the state of the game after a tick.
+    ///
+    /// # Parameters
+    /// - `game`: The game to record.
+    pub fn record(&mut self, game: &Game) -> io::Result<()> {
+        self.tick += 1;
+        let (x, y) = game.ball_position();
+        let state = match game.state() {
+            GameState::Running => "Running",
+            GameState::Lost => "Lost",
+            GameState::Won => "Won",
+            GameState::TimeUp => "TimeUp",
+        };
+        writeln!(
+            self.out,
+            "{:04} {x:.3} {y:.3} {} {state}",
+            self.tick,
+            game.score()
+        )
+    }
+
+    /// Flushes the trace written so far.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{GameEvent, GameOptions, InitialDir};
+    use crate::paddle::Direction;
+    use ratatui::prelude::Rect;
+
+    /// Plays a fixed game: a generated level, 24 FPS ticks and the paddle chasing the ball.
+    /// The spin is tamed so the ball stays slow enough not to skip over the walls.
+    fn trace() -> String {
+        let mut game = GameOptions::default()
+            .area(Rect::new(0, 0, 360, 180).into())
+            .ball_speed(48.)
+            .generator(7, 1)
+            .initial_direction(InitialDir::UpRight)
+            .death_animation(0.)
+            .paddle_friction(0.15)
+            .build();
+        let mut trace = TraceWriter::new(vec![]).unwrap();
+        for _ in 0..720 {
+            // The paddle chases the ball.
+            let (x, _) = game.ball_position();
+            let center = game.paddle_center();
+            if (x - center).abs() > 4. {
+                game.event(GameEvent::MovePad {
+                    direction: if x < center {
+                        Direction::Left
+                    } else {
+                        Direction::Right
+                    },
+                });
+            }
+            game.event(GameEvent::Tick { dt: 1. / 24. });
+            trace.record(&game).unwrap();
+        }
+        String::from_utf8(trace.out).unwrap()
+    }
+
+    #[test]
+    fn test_trace_matches_golden() {
+        let trace = trace();
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::write(
+                concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/trace.txt"),
+                &trace,
+            )
+            .unwrap();
+        }
+        assert_eq!(trace, include_str!("../tests/golden/trace.txt"));
+    }
+}
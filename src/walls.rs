@@ -1,15 +1,59 @@
 use crate::ball::{Ball, EllasticCollision};
 use crate::rectf64::Rectf64;
+use crate::render::GameRenderer;
 use ratatui::prelude::Color;
+#[cfg(feature = "tui")]
 use ratatui::widgets::canvas::{Painter, Shape};
 
-#[derive(Debug, Default)]
+/// Represents a single wall segment.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Wall {
-    area: Rectf64
+    /// The rectangular area occupied by the wall.
+    area: Rectf64,
+    /// Horizontal ranges `(start, end)` where the wall is open and the ball passes through.
+    gaps: Vec<(f64, f64)>,
+    /// The unit normal of the wall's surface, pointing into the playfield.
+    normal: (f64, f64),
+}
+
+impl Wall {
+    /// Returns the solid pieces of the wall, i.e. its area with the gaps cut out.
+    ///
+    /// # Returns
+    /// A list of rectangles making up the wall.
+    fn segments(&self) -> Vec<Rectf64> {
+        let mut gaps = self.gaps.clone();
+        gaps.sort_by(|g1, g2| g1.0.total_cmp(&g2.0));
+
+        let mut segments = vec![];
+        let mut x = self.area.left();
+        for (start, end) in gaps {
+            let start = start.clamp(self.area.left(), self.area.right());
+            let end = end.clamp(self.area.left(), self.area.right());
+            if start > x {
+                segments.push(Rectf64 {
+                    x,
+                    width: start - x,
+                    ..self.area.clone()
+                });
+            }
+            x = x.max(end);
+        }
+        if x < self.area.right() {
+            segments.push(Rectf64 {
+                x,
+                width: self.area.right() - x,
+                ..self.area.clone()
+            });
+        }
+        segments
+    }
 }
 
 /// Represents the walls of a game area, consisting of left, right, and top walls.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Walls {
     /// The rectangular area representing the left wall.
     pub left: Wall,
@@ -34,47 +78,128 @@ impl Walls {
     /// A new `Walls` instance with the specified areas and color.
     pub fn new(left: Rectf64, right: Rectf64, top: Rectf64, color: Color) -> Self {
         Self {
-            left: Wall { area: left},
-            right: Wall { area: right},
-            top: Wall { area: top },
+            left: Wall {
+                area: left,
+                gaps: vec![],
+                normal: (1., 0.),
+            },
+            right: Wall {
+                area: right,
+                gaps: vec![],
+                normal: (-1., 0.),
+            },
+            top: Wall {
+                area: top,
+                gaps: vec![],
+                normal: (0., -1.),
+            },
             color,
         }
     }
+
+    /// Opens gaps in the top wall.
+    ///
+    /// # Parameters
+    /// - `gaps`: Horizontal ranges `(start, end)` where the ball passes through the top wall.
+    ///
+    /// # Returns
+    /// The updated `Walls`.
+    pub fn top_gaps(mut self, gaps: Vec<(f64, f64)>) -> Self {
+        self.top.gaps = gaps;
+        self
+    }
 }
 
 impl EllasticCollision for Wall {
     /// Checks for and handles a collision with the given `Ball`.
     ///
-    /// If the ball intersects with any of the walls, the ball's velocity is reversed
-    /// along the appropriate axis.
+    /// If the ball intersects with the wall, it's reflected off the wall's surface.
     ///
     /// # Parameters
     /// - `ball`: The ball to check for collision.
     ///
     /// # Returns
     /// `true` if a collision occurred, `false` otherwise.
-    fn collide(&self, ball: &mut Ball)  {
-        if self.area.height < self.area.width {
-            ball.bouncev()
-        } else {
-            ball.bounceh()
-        }
-
+    fn collide(&self, ball: &mut Ball) {
+        ball.reflect(self.normal);
     }
 
     fn area(&self) -> Rectf64 {
         self.area.clone()
     }
+
+    /// The ball passes through the wall if it fits entirely inside one of the gaps.
+    fn lets_through(&self, ball: &Ball) -> bool {
+        let (x, _) = ball.position();
+        let radius = ball.radius();
+        self.gaps
+            .iter()
+            .any(|&(start, end)| x - radius >= start && x + radius <= end)
+    }
 }
 
+impl Walls {
+    /// Draws the walls with the given renderer.
+    ///
+    /// # Parameters
+    /// - `renderer`: The renderer to draw the walls with.
+    pub fn render<R: GameRenderer>(&self, renderer: &mut R) {
+        for wall in [&self.left, &self.right, &self.top] {
+            wall.segments()
+                .iter()
+                .for_each(|segment| renderer.fill_rect(segment, self.color));
+        }
+    }
+}
+
+#[cfg(feature = "tui")]
 impl Shape for Walls {
     /// Draws the walls on the given `Painter`.
     ///
     /// # Parameters
     /// - `painter`: The painter to draw the walls on.
     fn draw(&self, painter: &mut Painter) {
-        self.left.area.draw(painter, self.color);
-        self.right.area.draw(painter, self.color);
-        self.top.area.draw(painter, self.color);
+        self.render(painter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn top_wall(gaps: Vec<(f64, f64)>) -> Wall {
+        Wall {
+            area: Rectf64 {
+                x: 0.,
+                y: 100.,
+                width: 100.,
+                height: 2.,
+            },
+            gaps,
+            normal: (0., -1.),
+        }
+    }
+
+    #[test]
+    fn test_ball_passes_through_gap() {
+        let wall = top_wall(vec![(40., 60.)]);
+        let mut ball = Ball::new(50., 99., 3., 0., 2.);
+        assert!(!ball.collision(&wall));
+        ball.mov(1.);
+        assert_eq!(ball.position(), (50., 101.));
+
+        let mut ball = Ball::new(20., 99., 3., 0., 2.);
+        assert!(ball.collision(&wall));
+        ball.mov(1.);
+        assert_eq!(ball.position(), (20., 97.));
+    }
+
+    #[test]
+    fn test_segments() {
+        let wall = top_wall(vec![(40., 60.)]);
+        let segments = wall.segments();
+        assert_eq!(segments.len(), 2);
+        assert_eq!((segments[0].left(), segments[0].right()), (0., 40.));
+        assert_eq!((segments[1].left(), segments[1].right()), (60., 100.));
     }
 }
@@ -0,0 +1,63 @@
+use arkanoid_tui::rectf64::Rectf64;
+use arkanoid_tui::timestep::{FixedTimestep, MAX_CATCH_UP_STEPS};
+use arkanoid_tui::{GameEvent, GameOptions, GameState};
+
+#[test]
+fn test_game_runs_headlessly() {
+    let mut game = GameOptions::default()
+        .area(Rectf64 {
+            x: 0.,
+            y: 0.,
+            width: 360.,
+            height: 180.,
+        })
+        .ball_speed(48.)
+        .brick_count(10)
+        .seed(1)
+        .lives(1)
+        .death_animation(0.)
+        .build();
+    // Nobody moves the paddle, so the ball is lost sooner or later.
+    for _ in 0..10_000 {
+        if game.state() != GameState::Running {
+            break;
+        }
+        game.event(GameEvent::Tick { dt: 0.05 });
+    }
+    assert_eq!(game.state(), GameState::Lost);
+    assert_eq!(game.lives(), 0);
+    assert!(game.score() <= 10);
+}
+
+#[test]
+fn test_pause_keeps_the_partial_step() {
+    let run = |pause: bool| {
+        let mut game = GameOptions::default()
+            .area(Rectf64 {
+                x: 0.,
+                y: 0.,
+                width: 360.,
+                height: 180.,
+            })
+            .ball_speed(48.)
+            .brick_count(10)
+            .build();
+        // A power of two rate keeps the steps exact.
+        let mut timestep = FixedTimestep::new(64, MAX_CATCH_UP_STEPS);
+        let step = timestep.step();
+        let mut steps = timestep.advance(1.5 * step);
+        if pause {
+            timestep.set_paused(true);
+            steps += timestep.advance(10.);
+            timestep.set_paused(false);
+        }
+        steps += timestep.advance(0.5 * step);
+        for _ in 0..steps {
+            game.event(GameEvent::Tick { dt: step });
+        }
+        (steps, game.balls()[0].position())
+    };
+    let (steps, position) = run(false);
+    assert_eq!(steps, 2);
+    assert_eq!(run(true), (steps, position));
+}